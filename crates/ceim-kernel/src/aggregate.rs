@@ -0,0 +1,266 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    integrate, CeimKernel, CeimNodeImpact, Concentration, ConcentrationUnit, MassLoadError,
+    MassLoadOptions, MassLoadSample, RegulatoryLimits,
+};
+
+fn key(contaminant: &str) -> String {
+    contaminant.trim().to_ascii_lowercase()
+}
+
+/// A mixture member and its health-based water concentration (HBWC).
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct MixtureMember {
+    pub contaminant: String,
+    pub hbwc: f64,
+    pub unit: ConcentrationUnit,
+}
+
+impl MixtureMember {
+    fn new(contaminant: &str, hbwc: f64, unit: ConcentrationUnit) -> Self {
+        Self {
+            contaminant: contaminant.to_string(),
+            hbwc,
+            unit,
+        }
+    }
+
+    pub fn hbwc(&self) -> Concentration {
+        Concentration::new(self.hbwc, self.unit)
+    }
+}
+
+/// Hazard index `Σ C_i / HBWC_i` over the members present at the node, where
+/// `C_i` is the member's flow-weighted mean influent concentration. It is
+/// reported alongside K_n, not added to it: members' mass loads already count
+/// towards the node's K_n through their own limits.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct MixtureTerm {
+    pub name: String,
+    pub members: Vec<MixtureMember>,
+}
+
+impl MixtureTerm {
+    /// The EPA PFAS hazard index (40 CFR 141.61(c)). PFBS has no individual
+    /// MCL, so it appears here but not in the limits catalog.
+    pub fn pfas_hazard_index() -> Self {
+        Self {
+            name: "pfas_hazard_index".to_string(),
+            members: vec![
+                MixtureMember::new("pfhxs", 10.0, ConcentrationUnit::NgPerL),
+                MixtureMember::new("pfna", 10.0, ConcentrationUnit::NgPerL),
+                MixtureMember::new("hfpo-da", 10.0, ConcentrationUnit::NgPerL),
+                MixtureMember::new("pfbs", 2000.0, ConcentrationUnit::NgPerL),
+            ],
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct AggregationConfig {
+    /// Per-contaminant omega; contaminants not listed use `default_omega`.
+    pub omega: BTreeMap<String, f64>,
+    pub default_omega: f64,
+    pub mixtures: Vec<MixtureTerm>,
+}
+
+impl Default for AggregationConfig {
+    fn default() -> Self {
+        Self {
+            omega: BTreeMap::new(),
+            default_omega: 1.0,
+            mixtures: vec![MixtureTerm::pfas_hazard_index()],
+        }
+    }
+}
+
+impl AggregationConfig {
+    pub fn omega_for(&self, contaminant: &str) -> f64 {
+        self.omega
+            .iter()
+            .find(|(name, _)| key(name) == key(contaminant))
+            .map_or(self.default_omega, |(_, &w)| w)
+    }
+}
+
+/// One contaminant's readings at a node.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ContaminantSeries {
+    pub contaminant: String,
+    pub samples: Vec<MassLoadSample>,
+    pub limits: RegulatoryLimits,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct MixtureContribution {
+    pub name: String,
+    pub members_present: Vec<String>,
+    pub hazard_index: f64,
+    /// A hazard index above 1 exceeds the mixture standard.
+    pub exceeded: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NodeAggregate {
+    pub breakdown: Vec<CeimNodeImpact>,
+    pub mixtures: Vec<MixtureContribution>,
+    /// Σ ω·K_n; this is the node's ecoimpact score.
+    pub total_k_n: f64,
+    /// Contaminants with no limit; they add nothing to `total_k_n`.
+    pub unregulated: Vec<String>,
+}
+
+impl NodeAggregate {
+    pub fn impact(&self, contaminant: &str) -> Option<&CeimNodeImpact> {
+        self.breakdown
+            .iter()
+            .find(|i| key(&i.contaminant) == key(contaminant))
+    }
+}
+
+/// Flow-weighted mean influent concentration, `∫ c_in·Q dt / ∫ Q dt`;
+/// `None` when no water flowed.
+fn mean_influent(samples: &[MassLoadSample], options: &MassLoadOptions) -> Option<Concentration> {
+    let with = |c_in: fn(&MassLoadSample) -> Concentration| {
+        let samples: Vec<MassLoadSample> = samples
+            .iter()
+            .map(|s| MassLoadSample {
+                c_in: c_in(s),
+                c_out: Concentration::default(),
+                ..*s
+            })
+            .collect();
+        integrate(&samples, options).ok().map(|m| m.as_grams())
+    };
+    // 1 mg/L is 1 g/m³, so the load of a unit concentration is the volume.
+    let volume_m3 = with(|_| Concentration::mg_per_l(1.0))?;
+    let load_g = with(|s| s.c_in)?;
+    (volume_m3 > 0.0).then(|| Concentration::mg_per_l(load_g / volume_m3))
+}
+
+impl CeimKernel {
+    /// Node-level K_n across every contaminant in `series`.
+    pub fn aggregate(
+        series: &[ContaminantSeries],
+        config: &AggregationConfig,
+        options: &MassLoadOptions,
+    ) -> Result<NodeAggregate, MassLoadError> {
+        let mut breakdown = Vec::with_capacity(series.len());
+        for s in series {
+            let omega = config.omega_for(&s.contaminant);
+            breakdown.push(Self::compute_typed(
+                &s.contaminant,
+                omega,
+                &s.samples,
+                options,
+                &s.limits,
+            )?);
+        }
+
+        let mixtures: Vec<MixtureContribution> = config
+            .mixtures
+            .iter()
+            .filter_map(|term| {
+                let mut members_present = Vec::new();
+                let mut hazard_index = 0.0;
+                for s in series {
+                    let Some(member) = term
+                        .members
+                        .iter()
+                        .find(|m| key(&m.contaminant) == key(&s.contaminant))
+                    else {
+                        continue;
+                    };
+                    let Some(c) = mean_influent(&s.samples, options) else {
+                        continue;
+                    };
+                    members_present.push(s.contaminant.clone());
+                    hazard_index += c.as_mg_per_l() / member.hbwc().as_mg_per_l();
+                }
+                (!members_present.is_empty()).then(|| MixtureContribution {
+                    name: term.name.clone(),
+                    members_present,
+                    hazard_index,
+                    exceeded: hazard_index > 1.0,
+                })
+            })
+            .collect();
+
+        let total_k_n = breakdown.iter().map(|i| i.k_n).sum::<f64>();
+        let unregulated = breakdown
+            .iter()
+            .filter(|i| i.unregulated)
+            .map(|i| i.contaminant.clone())
+            .collect();
+        Ok(NodeAggregate {
+            breakdown,
+            mixtures,
+            total_k_n,
+            unregulated,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Concentration, FlowRate, Hours};
+
+    fn series(contaminant: &str, c_in_mg_l: f64, limit_mg_l: Option<f64>) -> ContaminantSeries {
+        let sample = |t| MassLoadSample {
+            t: Hours(t),
+            c_in: Concentration::mg_per_l(c_in_mg_l),
+            c_out: Concentration::default(),
+            flow: FlowRate::m3_per_h(1.0),
+        };
+        ContaminantSeries {
+            contaminant: contaminant.to_string(),
+            samples: vec![sample(0.0), sample(1.0)],
+            limits: RegulatoryLimits {
+                epa: limit_mg_l,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn weights_breakdown_and_mixture() {
+        let mut config = AggregationConfig::default();
+        config.omega.insert("Nitrate".into(), 0.5);
+        let ng = 1e-6;
+        let node = [
+            series("nitrate", 4.0, Some(2.0)),
+            series("PFNA", 5.0 * ng, Some(10.0 * ng)),
+            series("pfhxs", 10.0 * ng, Some(10.0 * ng)),
+            series("pfbs", 500.0 * ng, None),
+            series("novel", 9.0, None),
+        ];
+        let agg = CeimKernel::aggregate(&node, &config, &MassLoadOptions::default()).unwrap();
+        assert_eq!(agg.impact("nitrate").unwrap().k_n, 1.0);
+        assert!((agg.impact("pfna").unwrap().k_n - 0.5).abs() < 1e-12);
+        // Concentration over HBWC: 5/10 + 10/10 + 500/2000, PFBS included
+        // although it has no individual limit.
+        assert_eq!(agg.mixtures.len(), 1);
+        assert_eq!(agg.mixtures[0].members_present.len(), 3);
+        assert!((agg.mixtures[0].hazard_index - 1.75).abs() < 1e-9);
+        assert!(agg.mixtures[0].exceeded);
+        // The hazard index does not count the members' K_n a second time.
+        assert!((agg.total_k_n - (1.0 + 0.5 + 1.0)).abs() < 1e-9);
+        assert_eq!(
+            agg.unregulated,
+            vec!["pfbs".to_string(), "novel".to_string()]
+        );
+    }
+
+    #[test]
+    fn config_from_json_keeps_defaults() {
+        let config: AggregationConfig = serde_json::from_str(r#"{"omega": {"tds": 0.2}}"#).unwrap();
+        assert_eq!(config.omega_for("TDS"), 0.2);
+        assert_eq!(config.omega_for("lead"), 1.0);
+        assert_eq!(config.mixtures, vec![MixtureTerm::pfas_hazard_index()]);
+    }
+}
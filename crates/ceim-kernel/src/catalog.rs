@@ -0,0 +1,306 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::sync::OnceLock;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{Concentration, ConcentrationUnit, MixtureMember, MixtureTerm, RegulatoryLimits};
+
+/// Catalog shipped with the crate.
+pub const LIMITS_CATALOG_JSON: &str = include_str!("../data/limits.json");
+
+#[derive(Debug, Error)]
+pub enum CatalogError {
+    #[error("invalid limits catalog: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("limit for {contaminant} ({jurisdiction}) must be positive and finite")]
+    InvalidValue {
+        contaminant: String,
+        jurisdiction: String,
+    },
+    #[error("{contaminant} ({jurisdiction}) has two limits effective {effective}")]
+    DuplicateEntry {
+        contaminant: String,
+        jurisdiction: String,
+        effective: NaiveDate,
+    },
+    #[error("mixture {0} must have members and a positive, finite limit")]
+    InvalidMixture(String),
+    #[error("mixture {name} has two limits effective {effective}")]
+    DuplicateMixture { name: String, effective: NaiveDate },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Jurisdiction {
+    Epa,
+    Eu,
+    Who,
+    /// US state or other sub-national standard, e.g. `"NJ"`.
+    State(String),
+    Tribal(String),
+}
+
+impl Jurisdiction {
+    /// Key used in `RegulatoryLimits::additional`.
+    pub fn label(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for Jurisdiction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Jurisdiction::Epa => write!(f, "epa"),
+            Jurisdiction::Eu => write!(f, "eu"),
+            Jurisdiction::Who => write!(f, "who"),
+            Jurisdiction::State(name) => write!(f, "state:{name}"),
+            Jurisdiction::Tribal(name) => write!(f, "tribal:{name}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct LimitEntry {
+    pub contaminant: String,
+    pub jurisdiction: Jurisdiction,
+    pub value: f64,
+    pub unit: ConcentrationUnit,
+    pub source: String,
+    pub effective: NaiveDate,
+}
+
+impl LimitEntry {
+    pub fn concentration(&self) -> Concentration {
+        Concentration::new(self.value, self.unit)
+    }
+}
+
+/// A limit on the summed concentration of its members, e.g. the EU
+/// "Sum of PFAS". It binds the mixture, not each member on its own.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct MixtureLimit {
+    pub name: String,
+    pub jurisdiction: Jurisdiction,
+    pub members: Vec<String>,
+    pub value: f64,
+    pub unit: ConcentrationUnit,
+    pub source: String,
+    pub effective: NaiveDate,
+}
+
+impl MixtureLimit {
+    /// `Σ C_i <= L` as a hazard index: every member's HBWC is `L`.
+    pub fn term(&self) -> MixtureTerm {
+        MixtureTerm {
+            name: self.name.clone(),
+            members: self
+                .members
+                .iter()
+                .map(|contaminant| MixtureMember {
+                    contaminant: contaminant.clone(),
+                    hbwc: self.value,
+                    unit: self.unit,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Versioned limits keyed by contaminant and jurisdiction. Contaminant names
+/// are matched case-insensitively.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LimitsCatalog {
+    pub version: String,
+    pub entries: Vec<LimitEntry>,
+    #[serde(default)]
+    pub mixtures: Vec<MixtureLimit>,
+}
+
+fn key(contaminant: &str) -> String {
+    contaminant.trim().to_ascii_lowercase()
+}
+
+impl LimitsCatalog {
+    pub fn standard() -> &'static LimitsCatalog {
+        static STANDARD: OnceLock<LimitsCatalog> = OnceLock::new();
+        STANDARD
+            .get_or_init(|| Self::from_json(LIMITS_CATALOG_JSON).expect("bundled catalog is valid"))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, CatalogError> {
+        let mut catalog: LimitsCatalog = serde_json::from_str(json)?;
+        let mut seen = BTreeSet::new();
+        for entry in &mut catalog.entries {
+            entry.contaminant = key(&entry.contaminant);
+            if !(entry.value.is_finite() && entry.value > 0.0) {
+                return Err(CatalogError::InvalidValue {
+                    contaminant: entry.contaminant.clone(),
+                    jurisdiction: entry.jurisdiction.label(),
+                });
+            }
+            let id = (
+                entry.contaminant.clone(),
+                entry.jurisdiction.clone(),
+                entry.effective,
+            );
+            if !seen.insert(id) {
+                return Err(CatalogError::DuplicateEntry {
+                    contaminant: entry.contaminant.clone(),
+                    jurisdiction: entry.jurisdiction.label(),
+                    effective: entry.effective,
+                });
+            }
+        }
+        let mut seen = BTreeSet::new();
+        for mixture in &mut catalog.mixtures {
+            mixture.members = mixture.members.iter().map(|m| key(m)).collect();
+            if mixture.members.is_empty() || !(mixture.value.is_finite() && mixture.value > 0.0) {
+                return Err(CatalogError::InvalidMixture(mixture.name.clone()));
+            }
+            if !seen.insert((mixture.name.clone(), mixture.effective)) {
+                return Err(CatalogError::DuplicateMixture {
+                    name: mixture.name.clone(),
+                    effective: mixture.effective,
+                });
+            }
+        }
+        Ok(catalog)
+    }
+
+    pub fn contaminants(&self) -> BTreeSet<&str> {
+        self.entries
+            .iter()
+            .map(|e| e.contaminant.as_str())
+            .collect()
+    }
+
+    /// The entry in force on `on` for each jurisdiction: the latest one whose
+    /// effective date is not after `on`.
+    pub fn in_force(&self, contaminant: &str, on: NaiveDate) -> Vec<&LimitEntry> {
+        let contaminant = key(contaminant);
+        let mut latest: BTreeMap<&Jurisdiction, &LimitEntry> = BTreeMap::new();
+        for entry in &self.entries {
+            if entry.contaminant != contaminant || entry.effective > on {
+                continue;
+            }
+            let slot = latest.entry(&entry.jurisdiction).or_insert(entry);
+            if entry.effective > slot.effective {
+                *slot = entry;
+            }
+        }
+        latest.into_values().collect()
+    }
+
+    /// Limits in force on `on`, converted to mg/L. Empty when the catalog has
+    /// nothing for the contaminant.
+    pub fn limits_for(&self, contaminant: &str, on: NaiveDate) -> RegulatoryLimits {
+        let mut limits = RegulatoryLimits::default();
+        for entry in self.in_force(contaminant, on) {
+            let value = entry.concentration().as_mg_per_l();
+            match &entry.jurisdiction {
+                Jurisdiction::Epa => limits.epa = Some(value),
+                Jurisdiction::Eu => limits.eu = Some(value),
+                Jurisdiction::Who => limits.who = Some(value),
+                other => {
+                    limits.additional.insert(other.label(), value);
+                }
+            }
+        }
+        limits
+    }
+
+    /// Mixture limits in force on `on`, latest per name, as terms for
+    /// [`crate::AggregationConfig::mixtures`].
+    pub fn mixtures_in_force(&self, on: NaiveDate) -> Vec<MixtureTerm> {
+        let mut latest: BTreeMap<&str, &MixtureLimit> = BTreeMap::new();
+        for mixture in &self.mixtures {
+            if mixture.effective > on {
+                continue;
+            }
+            let slot = latest.entry(&mixture.name).or_insert(mixture);
+            if mixture.effective > slot.effective {
+                *slot = mixture;
+            }
+        }
+        latest.into_values().map(MixtureLimit::term).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn bundled_catalog_covers_core_contaminants() {
+        let catalog = LimitsCatalog::standard();
+        for c in ["pfoa", "pfos", "nitrate", "tds", "lead", "arsenic"] {
+            assert!(catalog.contaminants().contains(c), "{c} missing");
+        }
+        let pfoa = catalog.limits_for("PFOA", date("2026-06-01"));
+        assert!((pfoa.supreme().unwrap().value - 4e-6).abs() < 1e-15);
+        assert!((pfoa.additional["state:NJ"] - 1.4e-5).abs() < 1e-15);
+    }
+
+    #[test]
+    fn effective_dates_select_the_limit_in_force() {
+        let catalog = LimitsCatalog::standard();
+        let before = catalog.limits_for("lead", date("2026-01-01"));
+        let after = catalog.limits_for("lead", date("2028-01-01"));
+        assert_eq!(before.epa, Some(0.015));
+        assert_eq!(after.epa, Some(0.01));
+        let pfoa_2023 = catalog.limits_for("pfoa", date("2023-01-01"));
+        assert_eq!(pfoa_2023.epa, None);
+        assert_eq!(
+            catalog
+                .limits_for("unobtainium", date("2026-01-01"))
+                .supreme(),
+            None
+        );
+    }
+
+    #[test]
+    fn eu_pfas_limit_is_a_sum_not_a_per_species_limit() {
+        let catalog = LimitsCatalog::standard();
+        let on = date("2026-06-01");
+        assert_eq!(catalog.limits_for("pfoa", on).eu, None);
+        assert!(catalog.mixtures_in_force(date("2025-06-01")).is_empty());
+        let terms = catalog.mixtures_in_force(on);
+        let sum = terms.iter().find(|t| t.name == "eu_sum_of_pfas").unwrap();
+        assert_eq!(sum.members.len(), 20);
+        // 60 + 50 ng/L: each is under 100 ng/L, the sum is not.
+        let index: f64 = [("pfoa", 60e-6), ("pfos", 50e-6)]
+            .iter()
+            .map(|(c, mg_l)| {
+                let member = sum.members.iter().find(|m| m.contaminant == *c).unwrap();
+                mg_l / member.hbwc().as_mg_per_l()
+            })
+            .sum();
+        assert!(index > 1.0);
+    }
+
+    #[test]
+    fn rejects_duplicates_and_bad_values() {
+        let entry = r#"{"contaminant": "Lead", "jurisdiction": {"tribal": "Navajo Nation"},
+            "value": 10.0, "unit": "ug_per_l", "source": "x", "effective": "2020-01-01"}"#;
+        let dup = format!(r#"{{"version": "t", "entries": [{entry}, {entry}]}}"#);
+        assert!(matches!(
+            LimitsCatalog::from_json(&dup),
+            Err(CatalogError::DuplicateEntry { jurisdiction, .. }) if jurisdiction == "tribal:Navajo Nation"
+        ));
+        let bad = format!(
+            r#"{{"version": "t", "entries": [{}]}}"#,
+            entry.replace("10.0", "-1.0")
+        );
+        assert!(matches!(
+            LimitsCatalog::from_json(&bad),
+            Err(CatalogError::InvalidValue { .. })
+        ));
+    }
+}
@@ -1,12 +1,20 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{mass_load, RegulatoryLimits, SupremeLimit};
+use crate::{
+    integrate, MassLoadError, MassLoadOptions, MassLoadSample, RegulatoryLimits, SupremeLimit,
+};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CeimNodeImpact {
     pub contaminant: String,
     pub omega: f64,
+    /// 0.0 when `unregulated`; check the flag before reading it as "safe".
     pub k_n: f64,
+    pub mass_load_g: f64,
+    /// The limit K_n was normalised by, with its authority.
+    pub binding: Option<SupremeLimit>,
+    /// No authority sets a limit for this contaminant.
+    pub unregulated: bool,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -20,22 +28,77 @@ pub struct TimeSample {
 pub struct CeimKernel;
 
 impl CeimKernel {
+    /// K_n for legacy samples in hours, mg/L and m³/h.
     pub fn compute(
         contaminant: &str,
         omega: f64,
         samples: &[TimeSample],
         limits: &RegulatoryLimits,
-    ) -> CeimNodeImpact {
-        let supreme = limits.supreme();
-        let m_x = mass_load(samples);
-        let mut k_n = 0.0;
-        if supreme.value > 0.0 {
-            k_n = omega * m_x / supreme.value;
-        }
-        CeimNodeImpact {
+    ) -> Result<CeimNodeImpact, MassLoadError> {
+        let samples: Vec<MassLoadSample> = samples.iter().map(MassLoadSample::from).collect();
+        Self::compute_typed(
+            contaminant,
+            omega,
+            &samples,
+            &MassLoadOptions::default(),
+            limits,
+        )
+    }
+
+    /// K_n = ω · M / C_supreme with M in grams and limits in mg/L, so nodes
+    /// reporting in different units stay comparable.
+    pub fn compute_typed(
+        contaminant: &str,
+        omega: f64,
+        samples: &[MassLoadSample],
+        options: &MassLoadOptions,
+        limits: &RegulatoryLimits,
+    ) -> Result<CeimNodeImpact, MassLoadError> {
+        let binding = limits.supreme();
+        let m_x = integrate(samples, options)?.as_grams();
+        let k_n = binding.as_ref().map_or(0.0, |b| omega * m_x / b.value);
+        Ok(CeimNodeImpact {
             contaminant: contaminant.to_string(),
             omega,
             k_n,
-        }
+            mass_load_g: m_x,
+            unregulated: binding.is_none(),
+            binding,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples() -> Vec<TimeSample> {
+        [0.0, 2.0]
+            .into_iter()
+            .map(|t_hours| TimeSample {
+                t_hours,
+                c_in: 3.0,
+                c_out: 1.0,
+                flow_q: 1.0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn unregulated_is_flagged_not_zero_risk() {
+        let impact =
+            CeimKernel::compute("novel", 1.0, &samples(), &RegulatoryLimits::default()).unwrap();
+        assert!(impact.unregulated);
+        assert_eq!(impact.binding, None);
+        assert_eq!(impact.mass_load_g, 4.0);
+
+        let limits = RegulatoryLimits {
+            who: Some(2.0),
+            ..Default::default()
+        };
+        let impact = CeimKernel::compute("x", 1.0, &samples(), &limits).unwrap();
+        assert!(!impact.unregulated);
+        assert_eq!(impact.binding.unwrap().authority, "who");
+        assert_eq!(impact.k_n, 2.0);
     }
 }
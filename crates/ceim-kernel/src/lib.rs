@@ -1,7 +1,20 @@
+mod aggregate;
+mod catalog;
 mod ceim;
 mod mass_load;
 mod regulatory;
+mod units;
 
-pub use ceim::{CeimKernel, CeimNodeImpact};
-pub use mass_load::mass_load;
+pub use aggregate::{
+    AggregationConfig, ContaminantSeries, MixtureContribution, MixtureMember, MixtureTerm,
+    NodeAggregate,
+};
+pub use catalog::{
+    CatalogError, Jurisdiction, LimitEntry, LimitsCatalog, MixtureLimit, LIMITS_CATALOG_JSON,
+};
+pub use ceim::{CeimKernel, CeimNodeImpact, TimeSample};
+pub use mass_load::{
+    integrate, mass_load, GapPolicy, Integration, MassLoadError, MassLoadOptions, MassLoadSample,
+};
 pub use regulatory::{RegulatoryLimits, SupremeLimit};
+pub use units::{Concentration, ConcentrationUnit, FlowRate, FlowUnit, Hours, Mass};
@@ -1,22 +1,283 @@
-use crate::TimeSample;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
-pub fn mass_load(samples: &[TimeSample]) -> f64 {
-    if samples.is_empty() {
-        return 0.0;
+use crate::units::removal_rate;
+use crate::{Concentration, FlowRate, Hours, Mass, TimeSample};
+
+#[derive(Debug, Error, PartialEq)]
+pub enum MassLoadError {
+    #[error("sample {index} at t = {t_hours} h precedes the previous sample")]
+    OutOfOrder { index: usize, t_hours: f64 },
+    #[error("sample {index} duplicates the timestamp t = {t_hours} h")]
+    Duplicate { index: usize, t_hours: f64 },
+    #[error("gap of {gap_hours} h after t = {t_hours} h exceeds the {max_gap_hours} h limit")]
+    GapTooLarge {
+        t_hours: f64,
+        gap_hours: f64,
+        max_gap_hours: f64,
+    },
+    #[error("sample {0} has a non-finite value")]
+    NonFinite(usize),
+}
+
+/// A reading in explicit units; `t` is hours from the start of the series.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct MassLoadSample {
+    pub t: Hours,
+    pub c_in: Concentration,
+    pub c_out: Concentration,
+    pub flow: FlowRate,
+}
+
+impl From<&TimeSample> for MassLoadSample {
+    /// Legacy samples are hours, mg/L and m³/h.
+    fn from(s: &TimeSample) -> Self {
+        Self {
+            t: Hours(s.t_hours),
+            c_in: Concentration::mg_per_l(s.c_in),
+            c_out: Concentration::mg_per_l(s.c_out),
+            flow: FlowRate::m3_per_h(s.flow_q),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Integration {
+    #[default]
+    Trapezoid,
+    /// Simpson's 1/3 rule over pairs of equal-width intervals, falling back
+    /// to the trapezoid rule for unpaired intervals and gaps.
+    Simpson,
+}
+
+/// How intervals longer than `MassLoadOptions::max_gap` are integrated.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GapPolicy {
+    /// Linear between the bounding samples, i.e. the gap is integrated like
+    /// any other interval.
+    #[default]
+    Interpolate,
+    /// Carry the reading before the gap until the next sample.
+    Hold,
+    /// Fail with `MassLoadError::GapTooLarge`.
+    Reject,
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct MassLoadOptions {
+    #[serde(default)]
+    pub integration: Integration,
+    #[serde(default)]
+    pub gap_policy: GapPolicy,
+    /// Intervals longer than this are gaps; `None` treats none as gaps.
+    #[serde(default)]
+    pub max_gap: Option<Hours>,
+}
+
+/// Mass removed, ∫ (c_in − c_out)·Q dt, over hours, mg/L and m³/h samples.
+///
+/// The trapezoid averages the removal rate (c_in − c_out)·Q at the interval
+/// ends. Earlier versions multiplied the averaged concentration difference
+/// by the averaged flow, so results differ from theirs wherever both
+/// concentration and flow change across an interval.
+pub fn mass_load(samples: &[TimeSample]) -> Result<f64, MassLoadError> {
+    let samples: Vec<MassLoadSample> = samples.iter().map(MassLoadSample::from).collect();
+    integrate(&samples, &MassLoadOptions::default()).map(Mass::as_grams)
+}
+
+/// Integrate typed samples. Timestamps must be strictly increasing.
+pub fn integrate(
+    samples: &[MassLoadSample],
+    options: &MassLoadOptions,
+) -> Result<Mass, MassLoadError> {
+    for (index, s) in samples.iter().enumerate() {
+        let values = [
+            s.t.0,
+            s.c_in.as_mg_per_l(),
+            s.c_out.as_mg_per_l(),
+            s.flow.as_m3_per_h(),
+        ];
+        if values.iter().any(|v| !v.is_finite()) {
+            return Err(MassLoadError::NonFinite(index));
+        }
+        if index > 0 {
+            let prev = samples[index - 1].t.0;
+            let t_hours = s.t.0;
+            if t_hours == prev {
+                return Err(MassLoadError::Duplicate { index, t_hours });
+            }
+            if t_hours < prev {
+                return Err(MassLoadError::OutOfOrder { index, t_hours });
+            }
+        }
     }
+
+    let rates: Vec<f64> = samples
+        .iter()
+        .map(|s| removal_rate(s.c_in, s.c_out, s.flow))
+        .collect();
+    let dt = |i: usize| samples[i + 1].t.0 - samples[i].t.0;
+    let is_gap = |i: usize| options.max_gap.is_some_and(|max| dt(i) > max.0);
+
     let mut total = 0.0;
-    for w in samples.windows(2) {
-        let a = &w[0];
-        let b = &w[1];
-        let dt = b.t_hours - a.t_hours;
-        if dt <= 0.0 {
+    let mut i = 0;
+    while i + 1 < samples.len() {
+        if is_gap(i) {
+            total += match options.gap_policy {
+                GapPolicy::Interpolate => 0.5 * (rates[i] + rates[i + 1]) * dt(i),
+                GapPolicy::Hold => rates[i] * dt(i),
+                GapPolicy::Reject => {
+                    return Err(MassLoadError::GapTooLarge {
+                        t_hours: samples[i].t.0,
+                        gap_hours: dt(i),
+                        max_gap_hours: options.max_gap.map_or(f64::INFINITY, |h| h.0),
+                    })
+                }
+            };
+            i += 1;
             continue;
         }
-        let cin_minus_cout_a = a.c_in - a.c_out;
-        let cin_minus_cout_b = b.c_in - b.c_out;
-        let q_avg = 0.5 * (a.flow_q + b.flow_q);
-        let integrand_avg = 0.5 * (cin_minus_cout_a + cin_minus_cout_b);
-        total += integrand_avg * q_avg * dt;
+        let pairable = options.integration == Integration::Simpson
+            && i + 2 < samples.len()
+            && !is_gap(i + 1)
+            && (dt(i) - dt(i + 1)).abs() <= 1e-9 * dt(i);
+        if pairable {
+            total += dt(i) / 3.0 * (rates[i] + 4.0 * rates[i + 1] + rates[i + 2]);
+            i += 2;
+        } else {
+            total += 0.5 * (rates[i] + rates[i + 1]) * dt(i);
+            i += 1;
+        }
+    }
+    Ok(Mass::grams(total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConcentrationUnit, FlowUnit};
+
+    fn sample(t: f64, c_in: f64, flow: f64) -> MassLoadSample {
+        MassLoadSample {
+            t: Hours(t),
+            c_in: Concentration::mg_per_l(c_in),
+            c_out: Concentration::mg_per_l(0.0),
+            flow: FlowRate::m3_per_h(flow),
+        }
+    }
+
+    #[test]
+    fn rejects_unordered_and_duplicate_samples() {
+        let opts = MassLoadOptions::default();
+        let dup = [sample(0.0, 1.0, 1.0), sample(0.0, 2.0, 1.0)];
+        assert_eq!(
+            integrate(&dup, &opts),
+            Err(MassLoadError::Duplicate {
+                index: 1,
+                t_hours: 0.0
+            })
+        );
+        let back = [
+            sample(0.0, 1.0, 1.0),
+            sample(2.0, 1.0, 1.0),
+            sample(1.0, 1.0, 1.0),
+        ];
+        assert_eq!(
+            integrate(&back, &opts),
+            Err(MassLoadError::OutOfOrder {
+                index: 2,
+                t_hours: 1.0
+            })
+        );
+    }
+
+    #[test]
+    fn gap_policies() {
+        let samples = [
+            sample(0.0, 1.0, 1.0),
+            sample(1.0, 2.0, 1.0),
+            sample(5.0, 4.0, 1.0),
+        ];
+        let mut opts = MassLoadOptions {
+            max_gap: Some(Hours(2.0)),
+            ..Default::default()
+        };
+        assert_eq!(integrate(&samples, &opts).unwrap().as_grams(), 13.5);
+        assert_eq!(
+            integrate(&samples, &MassLoadOptions::default()),
+            integrate(&samples, &opts)
+        );
+        let parsed: MassLoadOptions =
+            serde_json::from_str(r#"{"gap_policy": "interpolate"}"#).unwrap();
+        assert_eq!(parsed.gap_policy, GapPolicy::Interpolate);
+        opts.gap_policy = GapPolicy::Hold;
+        assert_eq!(integrate(&samples, &opts).unwrap().as_grams(), 9.5);
+        opts.gap_policy = GapPolicy::Reject;
+        assert!(matches!(
+            integrate(&samples, &opts),
+            Err(MassLoadError::GapTooLarge { t_hours, .. }) if t_hours == 1.0
+        ));
+    }
+
+    #[test]
+    fn legacy_mass_load_averages_the_removal_rate() {
+        let samples = [
+            TimeSample {
+                t_hours: 0.0,
+                c_in: 1.0,
+                c_out: 0.0,
+                flow_q: 1.0,
+            },
+            TimeSample {
+                t_hours: 1.0,
+                c_in: 3.0,
+                c_out: 0.0,
+                flow_q: 3.0,
+            },
+        ];
+        // avg(c·Q) = 5 g; the pre-units avg(c)·avg(Q) gave 4 g.
+        assert_eq!(mass_load(&samples), Ok(5.0));
+    }
+
+    #[test]
+    fn simpson_is_exact_for_quadratics() {
+        // c_in = t², integral over 0..4 is 64/3.
+        let samples: Vec<_> = (0..=4)
+            .map(|t| sample(t as f64, (t * t) as f64, 1.0))
+            .collect();
+        let opts = MassLoadOptions {
+            integration: Integration::Simpson,
+            ..Default::default()
+        };
+        let simpson = integrate(&samples, &opts).unwrap().as_grams();
+        assert!((simpson - 64.0 / 3.0).abs() < 1e-12);
+        let trapezoid = integrate(&samples, &MassLoadOptions::default()).unwrap();
+        assert_eq!(trapezoid.as_grams(), 22.0);
+    }
+
+    #[test]
+    fn units_do_not_change_the_load() {
+        let metric = [sample(0.0, 0.004, 36.0), sample(2.0, 0.004, 36.0)];
+        let field = [
+            MassLoadSample {
+                t: Hours::from_minutes(0.0),
+                c_in: Concentration::new(4.0, ConcentrationUnit::UgPerL),
+                c_out: Concentration::default(),
+                flow: FlowRate::new(10.0, FlowUnit::LPerS),
+            },
+            MassLoadSample {
+                t: Hours::from_minutes(120.0),
+                c_in: Concentration::new(4000.0, ConcentrationUnit::NgPerL),
+                c_out: Concentration::default(),
+                flow: FlowRate::new(864.0, FlowUnit::M3PerDay),
+            },
+        ];
+        let opts = MassLoadOptions::default();
+        let a = integrate(&metric, &opts).unwrap().as_grams();
+        let b = integrate(&field, &opts).unwrap().as_grams();
+        assert!((a - 0.288).abs() < 1e-12);
+        assert!((a - b).abs() < 1e-12);
     }
-    total
 }
@@ -1,32 +1,70 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// Limits in mg/L. `additional` holds other jurisdictions, such as state or
+/// tribal standards, keyed by `Jurisdiction::label`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct RegulatoryLimits {
     pub epa: Option<f64>,
     pub eu: Option<f64>,
     pub who: Option<f64>,
+    #[serde(default)]
+    pub additional: BTreeMap<String, f64>,
 }
 
 impl RegulatoryLimits {
-    pub fn supreme(&self) -> SupremeLimit {
-        let mut candidates = Vec::new();
-        if let Some(v) = self.epa {
-            candidates.push(v);
-        }
-        if let Some(v) = self.eu {
-            candidates.push(v);
-        }
-        if let Some(v) = self.who {
-            candidates.push(v);
-        }
-        let value = candidates
+    /// The strictest positive, finite limit and the authority that set it.
+    /// `None` means the contaminant is unregulated here, not that it is safe.
+    /// Ties go to EPA, then EU, WHO and the additional jurisdictions.
+    pub fn supreme(&self) -> Option<SupremeLimit> {
+        let named = [("epa", self.epa), ("eu", self.eu), ("who", self.who)]
             .into_iter()
-            .fold(f64::INFINITY, |acc, v| if v < acc { v } else { acc });
-        SupremeLimit { value }
+            .filter_map(|(authority, v)| Some((authority.to_string(), v?)));
+        let additional = self.additional.iter().map(|(k, &v)| (k.clone(), v));
+        named
+            .chain(additional)
+            .filter(|(_, v)| v.is_finite() && *v > 0.0)
+            .fold(
+                None,
+                |best: Option<SupremeLimit>, (authority, value)| match best {
+                    Some(b) if b.value <= value => Some(b),
+                    _ => Some(SupremeLimit { authority, value }),
+                },
+            )
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// Binding limit in mg/L.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct SupremeLimit {
+    pub authority: String,
     pub value: f64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn supreme_names_the_binding_authority() {
+        let mut limits = RegulatoryLimits {
+            epa: Some(0.01),
+            eu: Some(0.01),
+            who: None,
+            additional: BTreeMap::new(),
+        };
+        assert_eq!(limits.supreme().unwrap().authority, "epa");
+        limits
+            .additional
+            .insert("tribal:Navajo Nation".into(), 0.005);
+        assert_eq!(
+            limits.supreme(),
+            Some(SupremeLimit {
+                authority: "tribal:Navajo Nation".into(),
+                value: 0.005
+            })
+        );
+        assert_eq!(RegulatoryLimits::default().supreme(), None);
+    }
+}
@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConcentrationUnit {
+    MgPerL,
+    UgPerL,
+    NgPerL,
+    /// Numerically equal to mg/L.
+    GPerM3,
+}
+
+impl ConcentrationUnit {
+    fn mg_per_l(self) -> f64 {
+        match self {
+            ConcentrationUnit::MgPerL | ConcentrationUnit::GPerM3 => 1.0,
+            ConcentrationUnit::UgPerL => 1e-3,
+            ConcentrationUnit::NgPerL => 1e-6,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FlowUnit {
+    M3PerH,
+    M3PerDay,
+    LPerS,
+    LPerMin,
+}
+
+impl FlowUnit {
+    fn m3_per_h(self) -> f64 {
+        match self {
+            FlowUnit::M3PerH => 1.0,
+            FlowUnit::M3PerDay => 1.0 / 24.0,
+            FlowUnit::LPerS => 3.6,
+            FlowUnit::LPerMin => 0.06,
+        }
+    }
+}
+
+/// Concentration, stored in mg/L.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, PartialOrd)]
+#[serde(transparent)]
+pub struct Concentration(f64);
+
+impl Concentration {
+    pub fn new(value: f64, unit: ConcentrationUnit) -> Self {
+        Self(value * unit.mg_per_l())
+    }
+
+    pub fn mg_per_l(value: f64) -> Self {
+        Self(value)
+    }
+
+    pub fn in_unit(self, unit: ConcentrationUnit) -> f64 {
+        self.0 / unit.mg_per_l()
+    }
+
+    pub fn as_mg_per_l(self) -> f64 {
+        self.0
+    }
+}
+
+/// Volumetric flow, stored in m³/h.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, PartialOrd)]
+#[serde(transparent)]
+pub struct FlowRate(f64);
+
+impl FlowRate {
+    pub fn new(value: f64, unit: FlowUnit) -> Self {
+        Self(value * unit.m3_per_h())
+    }
+
+    pub fn m3_per_h(value: f64) -> Self {
+        Self(value)
+    }
+
+    pub fn in_unit(self, unit: FlowUnit) -> f64 {
+        self.0 / unit.m3_per_h()
+    }
+
+    pub fn as_m3_per_h(self) -> f64 {
+        self.0
+    }
+}
+
+/// Elapsed time in hours.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, PartialOrd)]
+#[serde(transparent)]
+pub struct Hours(pub f64);
+
+impl Hours {
+    pub fn from_minutes(minutes: f64) -> Self {
+        Self(minutes / 60.0)
+    }
+
+    pub fn from_seconds(seconds: f64) -> Self {
+        Self(seconds / 3600.0)
+    }
+}
+
+/// Mass in grams; mg/L × m³/h × h integrates to grams.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, PartialOrd)]
+#[serde(transparent)]
+pub struct Mass(f64);
+
+impl Mass {
+    pub fn grams(value: f64) -> Self {
+        Self(value)
+    }
+
+    pub fn as_grams(self) -> f64 {
+        self.0
+    }
+
+    pub fn as_kg(self) -> f64 {
+        self.0 / 1000.0
+    }
+}
+
+/// Mass removal rate in g/h.
+pub(crate) fn removal_rate(c_in: Concentration, c_out: Concentration, flow: FlowRate) -> f64 {
+    (c_in.0 - c_out.0) * flow.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions_round_trip() {
+        let c = Concentration::new(4.0, ConcentrationUnit::NgPerL);
+        assert!((c.as_mg_per_l() - 4e-6).abs() < 1e-18);
+        assert!((c.in_unit(ConcentrationUnit::UgPerL) - 4e-3).abs() < 1e-15);
+        let q = FlowRate::new(10.0, FlowUnit::LPerS);
+        assert!((q.as_m3_per_h() - 36.0).abs() < 1e-12);
+        assert!((q.in_unit(FlowUnit::M3PerDay) - 864.0).abs() < 1e-9);
+        assert_eq!(Hours::from_minutes(90.0), Hours(1.5));
+    }
+}
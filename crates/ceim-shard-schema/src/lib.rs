@@ -0,0 +1,91 @@
+mod migrate;
+
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shard_store::{ShardEntry, ShardStore, ShardStoreError};
+use thiserror::Error;
+
+pub use migrate::migrate;
+
+/// Version written by this crate. Bump it with a migration in `migrate.rs`.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// File prefix CEIM shards use in a `ShardStore`.
+pub const SHARD_PREFIX: &str = "ceim_shard";
+
+#[derive(Debug, Error)]
+pub enum SchemaError {
+    #[error("invalid shard: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("shard schema version {0} is newer than supported version {SCHEMA_VERSION}")]
+    UnsupportedVersion(u32),
+    #[error("shard v1 is malformed: {0}")]
+    MalformedLegacy(&'static str),
+    #[error(transparent)]
+    Store(#[from] ShardStoreError),
+}
+
+/// Time span covered by the samples behind a K_n value.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SampleWindow {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CeimNodeState {
+    pub node_id: String,
+    pub contaminant: String,
+    pub k_n: f64,
+    /// `None` for shards migrated from versions that did not record it.
+    pub mass_load_g: Option<f64>,
+    /// Authority whose limit normalised `k_n`; `None` when unregulated.
+    pub binding_authority: Option<String>,
+    pub unregulated: bool,
+    /// Aggregate over every contaminant at the node.
+    pub ecoimpact_score: f64,
+    pub window: Option<SampleWindow>,
+    pub last_updated: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CeimShard {
+    pub schema_version: u32,
+    pub generated_at: DateTime<Utc>,
+    pub nodes: Vec<CeimNodeState>,
+}
+
+impl CeimShard {
+    pub fn new(generated_at: DateTime<Utc>, nodes: Vec<CeimNodeState>) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            generated_at,
+            nodes,
+        }
+    }
+
+    /// Parse a shard of any supported version into the current schema.
+    pub fn from_json(raw: &[u8]) -> Result<Self, SchemaError> {
+        migrate(serde_json::from_slice(raw)?)
+    }
+}
+
+pub fn write_shard(store: &mut ShardStore, shard: &CeimShard) -> Result<ShardEntry, SchemaError> {
+    Ok(store.write(shard.generated_at, shard)?)
+}
+
+pub fn read_shard(store: &ShardStore, entry: &ShardEntry) -> Result<CeimShard, SchemaError> {
+    migrate(store.read(entry)?)
+}
+
+/// Newest shard in the store at `dir`, migrated to the current schema.
+pub fn load_latest(dir: impl AsRef<Path>) -> Result<Option<CeimShard>, SchemaError> {
+    let store = ShardStore::open_existing(dir.as_ref(), SHARD_PREFIX)?;
+    store
+        .entries()
+        .last()
+        .map(|entry| read_shard(&store, entry))
+        .transpose()
+}
@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Value};
+
+use crate::{CeimShard, SchemaError, SCHEMA_VERSION};
+
+/// Bring a shard value up to `SCHEMA_VERSION`, one version at a time.
+/// Shards without `schema_version` are v1: `generated_at` was any RFC 3339
+/// string and nodes carried only `node_id`, `contaminant`, `k_n` and
+/// `last_updated`, sometimes `ecoimpact_score`.
+pub fn migrate(mut value: Value) -> Result<CeimShard, SchemaError> {
+    let mut version = value
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .unwrap_or(1) as u32;
+    if version > SCHEMA_VERSION {
+        return Err(SchemaError::UnsupportedVersion(version));
+    }
+    while version < SCHEMA_VERSION {
+        value = match version {
+            1 => v1_to_v2(value)?,
+            _ => unreachable!("every version below SCHEMA_VERSION has a step"),
+        };
+        version += 1;
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+/// v2 adds the schema version, CEIM provenance fields and the node-level
+/// `ecoimpact_score`, which v1 readers expected but v1 writers never set.
+/// Missing scores become the sum of the node's K_n.
+fn v1_to_v2(value: Value) -> Result<Value, SchemaError> {
+    let Value::Object(mut shard) = value else {
+        return Err(SchemaError::MalformedLegacy("shard is not an object"));
+    };
+    let generated_at = shard
+        .get("generated_at")
+        .and_then(Value::as_str)
+        .and_then(|s| s.parse::<DateTime<Utc>>().ok())
+        .ok_or(SchemaError::MalformedLegacy("generated_at"))?;
+    let Some(Value::Array(nodes)) = shard.remove("nodes") else {
+        return Err(SchemaError::MalformedLegacy("nodes"));
+    };
+
+    let mut node_totals: HashMap<String, f64> = HashMap::new();
+    for node in &nodes {
+        let id = node
+            .get("node_id")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let k_n = node.get("k_n").and_then(Value::as_f64).unwrap_or_default();
+        *node_totals.entry(id.to_string()).or_default() += k_n;
+    }
+
+    let nodes = nodes
+        .into_iter()
+        .map(|node| {
+            let Value::Object(mut node) = node else {
+                return Err(SchemaError::MalformedLegacy("node is not an object"));
+            };
+            let id = node
+                .get("node_id")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            let mut fill = |key: &str, default: Value| {
+                node.entry(key.to_string()).or_insert(default);
+            };
+            fill("ecoimpact_score", Value::from(node_totals[&id]));
+            fill("mass_load_g", Value::Null);
+            fill("binding_authority", Value::Null);
+            fill("unregulated", Value::Bool(false));
+            fill("window", Value::Null);
+            fill("last_updated", Value::from(generated_at.to_rfc3339()));
+            Ok(Value::Object(node))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut out = Map::new();
+    out.insert("schema_version".into(), Value::from(2));
+    out.insert(
+        "generated_at".into(),
+        Value::from(generated_at.to_rfc3339()),
+    );
+    out.insert("nodes".into(), Value::Array(nodes));
+    Ok(Value::Object(out))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const V1: &str = include_str!("../fixtures/ceim_shard_v1.json");
+
+    #[test]
+    fn v1_bridge_shard_migrates() {
+        let shard = CeimShard::from_json(V1.as_bytes()).unwrap();
+        assert_eq!(shard.schema_version, SCHEMA_VERSION);
+        assert_eq!(shard.nodes.len(), 2);
+        for node in &shard.nodes {
+            assert!((node.ecoimpact_score - 0.6).abs() < 1e-12);
+            assert_eq!(node.mass_load_g, None);
+            assert!(!node.unregulated);
+        }
+    }
+
+    #[test]
+    fn current_version_round_trips_and_future_is_rejected() {
+        let shard = CeimShard::from_json(V1.as_bytes()).unwrap();
+        let json = serde_json::to_vec(&shard).unwrap();
+        assert_eq!(CeimShard::from_json(&json).unwrap(), shard);
+
+        let future = format!(
+            r#"{{"schema_version": {}, "generated_at": "2030-01-01T00:00:00Z", "nodes": []}}"#,
+            SCHEMA_VERSION + 1
+        );
+        assert!(matches!(
+            CeimShard::from_json(future.as_bytes()),
+            Err(SchemaError::UnsupportedVersion(v)) if v == SCHEMA_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn loads_a_directory_of_baseline_shards() {
+        let dir = std::env::temp_dir().join(format!("ceim-v1-dir-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        // File names as the baseline bridge wrote them, with no manifest.
+        let older = V1.replace("2025-02-01T06:00:00.123456", "2025-02-01T05:00:00");
+        std::fs::write(dir.join("ceim_shard_2025-02-01T05_00_00+00_00.json"), older).unwrap();
+        std::fs::write(
+            dir.join("ceim_shard_2025-02-01T06_00_00.123456+00_00.json"),
+            V1,
+        )
+        .unwrap();
+
+        let latest = crate::load_latest(&dir).unwrap().unwrap();
+        assert_eq!(latest, CeimShard::from_json(V1.as_bytes()).unwrap());
+
+        let store = shard_store::ShardStore::open(&dir, crate::SHARD_PREFIX).unwrap();
+        assert_eq!(store.entries().len(), 2);
+        let first = crate::read_shard(&store, &store.entries()[0]).unwrap();
+        assert_eq!(first.schema_version, SCHEMA_VERSION);
+        assert_eq!(first.generated_at.to_rfc3339(), "2025-02-01T05:00:00+00:00");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,285 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{ViabilityCheck, ViabilityEnvelope, ViabilityState};
+
+#[derive(Debug, Error, PartialEq)]
+pub enum DynamicsError {
+    #[error("flow profile needs at least one point with non-decreasing, finite times")]
+    InvalidProfile,
+    #[error("time step must be positive and finite, got {0}")]
+    InvalidStep(f64),
+    #[error("asset dynamics parameters must be positive and finite")]
+    InvalidParameters,
+    #[error("window needs {steps} steps, more than the limit of {MAX_REACHABILITY_STEPS}")]
+    TooManySteps { steps: f64 },
+}
+
+/// Most steps [`check_reachability`] will project in one call.
+pub const MAX_REACHABILITY_STEPS: usize = 100_000;
+
+/// First-order asset response to flow. Load and vibration follow
+/// `flow / rated_flow_q` with time constant `load_tau_h`; temperature follows
+/// `ambient_c + thermal_gain_c * load` with time constant `thermal_tau_h`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AssetDynamics {
+    pub rated_flow_q: f64,
+    pub load_tau_h: f64,
+    pub vibration_per_load: f64,
+    pub ambient_c: f64,
+    pub thermal_gain_c: f64,
+    pub thermal_tau_h: f64,
+}
+
+impl Default for AssetDynamics {
+    fn default() -> Self {
+        Self {
+            rated_flow_q: 1.0,
+            load_tau_h: 0.5,
+            vibration_per_load: 0.6,
+            ambient_c: 20.0,
+            thermal_gain_c: 15.0,
+            thermal_tau_h: 2.0,
+        }
+    }
+}
+
+impl AssetDynamics {
+    fn validate(&self) -> Result<(), DynamicsError> {
+        let positive = [self.rated_flow_q, self.load_tau_h, self.thermal_tau_h];
+        let finite = [self.vibration_per_load, self.ambient_c, self.thermal_gain_c];
+        if positive.iter().all(|v| v.is_finite() && *v > 0.0)
+            && finite.iter().all(|v| v.is_finite())
+        {
+            Ok(())
+        } else {
+            Err(DynamicsError::InvalidParameters)
+        }
+    }
+
+    /// State the asset settles at under constant `flow_q`.
+    pub fn steady_state(&self, flow_q: f64) -> ViabilityState {
+        let load = flow_q / self.rated_flow_q;
+        ViabilityState {
+            load_fraction: load,
+            vibration_index: self.vibration_per_load * load,
+            temperature_c: self.ambient_c + self.thermal_gain_c * load,
+        }
+    }
+
+    fn step(&self, state: &ViabilityState, flow_q: f64, dt_h: f64) -> ViabilityState {
+        let target = self.steady_state(flow_q);
+        let k_load = 1.0 - (-dt_h / self.load_tau_h).exp();
+        let k_heat = 1.0 - (-dt_h / self.thermal_tau_h).exp();
+        ViabilityState {
+            load_fraction: state.load_fraction
+                + k_load * (target.load_fraction - state.load_fraction),
+            vibration_index: state.vibration_index
+                + k_load * (target.vibration_index - state.vibration_index),
+            temperature_c: state.temperature_c
+                + k_heat * (target.temperature_c - state.temperature_c),
+        }
+    }
+}
+
+/// Piecewise-linear flow over time, hours from the start of the window.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct FlowProfile {
+    pub points: Vec<(f64, f64)>,
+}
+
+impl FlowProfile {
+    pub fn new(points: Vec<(f64, f64)>) -> Result<Self, DynamicsError> {
+        let ordered = points.windows(2).all(|w| w[0].0 <= w[1].0);
+        let finite = points.iter().all(|(t, q)| t.is_finite() && q.is_finite());
+        if points.is_empty() || !ordered || !finite {
+            return Err(DynamicsError::InvalidProfile);
+        }
+        Ok(Self { points })
+    }
+
+    pub fn duration_h(&self) -> f64 {
+        self.points.last().map_or(0.0, |p| p.0) - self.points[0].0
+    }
+
+    pub fn flow_at(&self, t_h: f64) -> f64 {
+        let t = self.points[0].0 + t_h;
+        match self.points.iter().position(|p| p.0 >= t) {
+            None => self.points.last().expect("non-empty").1,
+            Some(0) => self.points[0].1,
+            Some(i) => {
+                let (t0, q0) = self.points[i - 1];
+                let (t1, q1) = self.points[i];
+                if t1 == t0 {
+                    q1
+                } else {
+                    q0 + (q1 - q0) * (t - t0) / (t1 - t0)
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TrajectoryPoint {
+    pub t_hours: f64,
+    pub state: ViabilityState,
+}
+
+/// Where a projected trajectory first left the envelope.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ReachabilityExit {
+    pub step: usize,
+    pub t_hours: f64,
+    pub check: ViabilityCheck,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ReachabilityReport {
+    pub trajectory: Vec<TrajectoryPoint>,
+    pub first_exit: Option<ReachabilityExit>,
+}
+
+impl ReachabilityReport {
+    pub fn stays_viable(&self) -> bool {
+        self.first_exit.is_none()
+    }
+
+    pub fn states(&self) -> Vec<ViabilityState> {
+        self.trajectory.iter().map(|p| p.state.clone()).collect()
+    }
+}
+
+/// Project `start` through `profile` in steps of `dt_h` (the last step is
+/// shortened to land on the end of the window) and check every state.
+/// Windows needing more than [`MAX_REACHABILITY_STEPS`] steps are rejected.
+pub fn check_reachability(
+    envelope: &ViabilityEnvelope,
+    dynamics: &AssetDynamics,
+    start: &ViabilityState,
+    profile: &FlowProfile,
+    dt_h: f64,
+) -> Result<ReachabilityReport, DynamicsError> {
+    if !(dt_h.is_finite() && dt_h > 0.0) {
+        return Err(DynamicsError::InvalidStep(dt_h));
+    }
+    dynamics.validate()?;
+
+    let duration = profile.duration_h();
+    let steps = (duration / dt_h).ceil();
+    if steps > MAX_REACHABILITY_STEPS as f64 {
+        return Err(DynamicsError::TooManySteps { steps });
+    }
+    let mut trajectory = vec![TrajectoryPoint {
+        t_hours: 0.0,
+        state: start.clone(),
+    }];
+    let mut t = 0.0;
+    while t < duration {
+        let dt = dt_h.min(duration - t);
+        let flow = profile.flow_at(t + 0.5 * dt);
+        let state = dynamics.step(&trajectory.last().expect("non-empty").state, flow, dt);
+        t += dt;
+        trajectory.push(TrajectoryPoint { t_hours: t, state });
+    }
+
+    let first_exit = trajectory.iter().enumerate().find_map(|(step, point)| {
+        let check = envelope.check(&point.state);
+        (!check.is_viable()).then_some(ReachabilityExit {
+            step,
+            t_hours: point.t_hours,
+            check,
+        })
+    });
+    Ok(ReachabilityReport {
+        trajectory,
+        first_exit,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AssetClass, ViabilityDimension};
+
+    fn start() -> ViabilityState {
+        ViabilityState {
+            load_fraction: 0.3,
+            vibration_index: 0.2,
+            temperature_c: 24.0,
+        }
+    }
+
+    #[test]
+    fn moderate_flow_stays_viable() {
+        let profile = FlowProfile::new(vec![(0.0, 0.8), (6.0, 0.8)]).unwrap();
+        let report = check_reachability(
+            &ViabilityEnvelope::preset(AssetClass::Intake),
+            &AssetDynamics::default(),
+            &start(),
+            &profile,
+            0.25,
+        )
+        .unwrap();
+        assert!(report.stays_viable());
+        assert_eq!(report.trajectory.len(), 25);
+        let last = &report.trajectory.last().unwrap().state;
+        assert!((last.load_fraction - 0.8).abs() < 1e-3);
+    }
+
+    #[test]
+    fn detects_mid_window_exit() {
+        // Viable at both ends of the window, but the flow peak overloads.
+        let profile =
+            FlowProfile::new(vec![(0.0, 0.5), (3.0, 1.6), (6.0, 0.5), (12.0, 0.5)]).unwrap();
+        let envelope = ViabilityEnvelope::preset(AssetClass::Intake);
+        let report = check_reachability(
+            &envelope,
+            &AssetDynamics::default(),
+            &start(),
+            &profile,
+            0.5,
+        )
+        .unwrap();
+        let exit = report.first_exit.as_ref().expect("leaves envelope");
+        assert!(exit.t_hours > 0.0 && exit.t_hours < 12.0);
+        assert!(exit
+            .check
+            .violations
+            .iter()
+            .any(|v| v.dimension == ViabilityDimension::LoadFraction));
+        assert!(envelope
+            .check(&report.trajectory.last().unwrap().state)
+            .is_viable());
+    }
+
+    #[test]
+    fn rejects_bad_inputs() {
+        assert_eq!(
+            FlowProfile::new(vec![(1.0, 0.5), (0.0, 0.5)]),
+            Err(DynamicsError::InvalidProfile)
+        );
+        let profile = FlowProfile::new(vec![(0.0, 0.5), (1.0, 0.5)]).unwrap();
+        assert_eq!(
+            check_reachability(
+                &ViabilityEnvelope::default(),
+                &AssetDynamics::default(),
+                &start(),
+                &profile,
+                0.0
+            ),
+            Err(DynamicsError::InvalidStep(0.0))
+        );
+        let long = FlowProfile::new(vec![(0.0, 0.5), (1000.0, 0.5)]).unwrap();
+        assert_eq!(
+            check_reachability(
+                &ViabilityEnvelope::default(),
+                &AssetDynamics::default(),
+                &start(),
+                &long,
+                0.001
+            ),
+            Err(DynamicsError::TooManySteps { steps: 1.0e6 })
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::ViabilityState;
+
+#[derive(Debug, Error)]
+pub enum EnvelopeError {
+    #[error("invalid envelope config: {0}")]
+    Config(#[from] serde_json::Error),
+    #[error("bound for {0:?} must be finite with min <= max")]
+    InvalidBound(ViabilityDimension),
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum ViabilityDimension {
+    LoadFraction,
+    VibrationIndex,
+    TemperatureC,
+}
+
+impl ViabilityDimension {
+    pub const ALL: [ViabilityDimension; 3] = [
+        ViabilityDimension::LoadFraction,
+        ViabilityDimension::VibrationIndex,
+        ViabilityDimension::TemperatureC,
+    ];
+
+    pub fn value(self, state: &ViabilityState) -> f64 {
+        match self {
+            ViabilityDimension::LoadFraction => state.load_fraction,
+            ViabilityDimension::VibrationIndex => state.vibration_index,
+            ViabilityDimension::TemperatureC => state.temperature_c,
+        }
+    }
+}
+
+/// Asset classes with preset envelopes.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetClass {
+    Pump,
+    Membrane,
+    Intake,
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct Bound {
+    #[serde(default)]
+    pub min: Option<f64>,
+    #[serde(default)]
+    pub max: Option<f64>,
+}
+
+impl Bound {
+    pub fn at_most(max: f64) -> Self {
+        Self {
+            min: None,
+            max: Some(max),
+        }
+    }
+
+    pub fn between(min: f64, max: f64) -> Self {
+        Self {
+            min: Some(min),
+            max: Some(max),
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        let finite = |v: Option<f64>| v.is_none_or(f64::is_finite);
+        finite(self.min)
+            && finite(self.max)
+            && match (self.min, self.max) {
+                (Some(lo), Some(hi)) => lo <= hi,
+                _ => true,
+            }
+    }
+
+    /// Signed distance to the nearest bound; negative when outside.
+    fn margin(&self, value: f64) -> f64 {
+        if value.is_nan() {
+            return f64::NEG_INFINITY;
+        }
+        let below = self.max.map_or(f64::INFINITY, |hi| hi - value);
+        let above = self.min.map_or(f64::INFINITY, |lo| value - lo);
+        below.min(above)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BoundSide {
+    Min,
+    Max,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct EnvelopeViolation {
+    pub dimension: ViabilityDimension,
+    pub side: BoundSide,
+    pub limit: f64,
+    pub value: f64,
+    /// How far past the limit; infinite when the value is not a number.
+    pub excess: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ViabilityCheck {
+    pub envelope: String,
+    /// Signed margin to the nearest bound per dimension; negative outside.
+    pub margins: BTreeMap<ViabilityDimension, f64>,
+    pub violations: Vec<EnvelopeViolation>,
+}
+
+impl ViabilityCheck {
+    pub fn is_viable(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Named per-dimension bounds. Dimensions without a bound are unconstrained.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ViabilityEnvelope {
+    pub name: String,
+    pub bounds: BTreeMap<ViabilityDimension, Bound>,
+}
+
+/// Config form: an optional preset with per-dimension overrides.
+#[derive(Deserialize)]
+struct EnvelopeConfig {
+    name: Option<String>,
+    preset: Option<AssetClass>,
+    #[serde(default)]
+    bounds: BTreeMap<ViabilityDimension, Bound>,
+}
+
+impl Default for ViabilityEnvelope {
+    /// Load ≤ 1.0, vibration ≤ 1.0, 0–60 °C.
+    fn default() -> Self {
+        Self::from_bounds(
+            "default",
+            [
+                Bound::at_most(1.0),
+                Bound::at_most(1.0),
+                Bound::between(0.0, 60.0),
+            ],
+        )
+    }
+}
+
+impl ViabilityEnvelope {
+    fn from_bounds(name: &str, bounds: [Bound; 3]) -> Self {
+        Self {
+            name: name.to_string(),
+            bounds: ViabilityDimension::ALL.into_iter().zip(bounds).collect(),
+        }
+    }
+
+    pub fn preset(class: AssetClass) -> Self {
+        match class {
+            AssetClass::Pump => Self::from_bounds(
+                "pump",
+                [
+                    Bound::at_most(1.0),
+                    Bound::at_most(0.8),
+                    Bound::between(0.0, 70.0),
+                ],
+            ),
+            AssetClass::Membrane => Self::from_bounds(
+                "membrane",
+                [
+                    Bound::at_most(0.9),
+                    Bound::at_most(0.5),
+                    Bound::between(5.0, 45.0),
+                ],
+            ),
+            AssetClass::Intake => Self::from_bounds(
+                "intake",
+                [
+                    Bound::at_most(1.0),
+                    Bound::at_most(0.9),
+                    Bound::between(0.0, 40.0),
+                ],
+            ),
+        }
+    }
+
+    /// Parse `{"name": .., "preset": "membrane", "bounds": {"temperature_c": {"max": 40}}}`.
+    /// Bounds given in the config replace the preset's bound for that dimension.
+    pub fn from_json(json: &str) -> Result<Self, EnvelopeError> {
+        let config: EnvelopeConfig = serde_json::from_str(json)?;
+        let mut envelope = match config.preset {
+            Some(class) => Self::preset(class),
+            None => Self {
+                name: String::new(),
+                bounds: BTreeMap::new(),
+            },
+        };
+        if let Some(name) = config.name {
+            envelope.name = name;
+        }
+        for (dimension, bound) in config.bounds {
+            if !bound.is_valid() {
+                return Err(EnvelopeError::InvalidBound(dimension));
+            }
+            envelope.bounds.insert(dimension, bound);
+        }
+        Ok(envelope)
+    }
+
+    pub fn margins(&self, state: &ViabilityState) -> BTreeMap<ViabilityDimension, f64> {
+        self.bounds
+            .iter()
+            .map(|(&dimension, bound)| (dimension, bound.margin(dimension.value(state))))
+            .collect()
+    }
+
+    pub fn check(&self, state: &ViabilityState) -> ViabilityCheck {
+        let mut violations = Vec::new();
+        for (&dimension, bound) in &self.bounds {
+            let value = dimension.value(state);
+            let sides = [(BoundSide::Min, bound.min), (BoundSide::Max, bound.max)];
+            for (side, limit) in sides {
+                let Some(limit) = limit else { continue };
+                let excess = match side {
+                    BoundSide::Min => limit - value,
+                    BoundSide::Max => value - limit,
+                };
+                if value.is_nan() || excess > 0.0 {
+                    violations.push(EnvelopeViolation {
+                        dimension,
+                        side,
+                        limit,
+                        value,
+                        excess: if value.is_nan() {
+                            f64::INFINITY
+                        } else {
+                            excess
+                        },
+                    });
+                }
+            }
+        }
+        ViabilityCheck {
+            envelope: self.name.clone(),
+            margins: self.margins(state),
+            violations,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(temp: f64) -> ViabilityState {
+        ViabilityState {
+            load_fraction: 0.7,
+            vibration_index: 0.4,
+            temperature_c: temp,
+        }
+    }
+
+    #[test]
+    fn reports_dimension_and_excess() {
+        let check = ViabilityEnvelope::preset(AssetClass::Membrane).check(&state(48.0));
+        assert!(!check.is_viable());
+        assert_eq!(check.violations.len(), 1);
+        let v = &check.violations[0];
+        assert_eq!(
+            (v.dimension, v.side),
+            (ViabilityDimension::TemperatureC, BoundSide::Max)
+        );
+        assert!((v.excess - 3.0).abs() < 1e-9);
+        assert!((check.margins[&ViabilityDimension::LoadFraction] - 0.2).abs() < 1e-9);
+        assert!(check.margins[&ViabilityDimension::TemperatureC] < 0.0);
+    }
+
+    #[test]
+    fn config_overrides_preset() {
+        let envelope = ViabilityEnvelope::from_json(
+            r#"{"name": "cybo-intake-3", "preset": "intake",
+                "bounds": {"temperature_c": {"min": 2.0, "max": 32.0}}}"#,
+        )
+        .unwrap();
+        assert_eq!(envelope.name, "cybo-intake-3");
+        assert!(envelope.check(&state(30.0)).is_viable());
+        assert!(!envelope.check(&state(1.0)).is_viable());
+        assert!(matches!(
+            ViabilityEnvelope::from_json(
+                r#"{"bounds": {"load_fraction": {"min": 2.0, "max": 1.0}}}"#
+            ),
+            Err(EnvelopeError::InvalidBound(
+                ViabilityDimension::LoadFraction
+            ))
+        ));
+    }
+
+    #[test]
+    fn nan_is_never_viable() {
+        let check = ViabilityEnvelope::default().check(&state(f64::NAN));
+        assert_eq!(check.violations.len(), 2);
+        assert!(check.violations.iter().all(|v| v.excess.is_infinite()));
+    }
+}
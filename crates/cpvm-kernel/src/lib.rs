@@ -1,5 +1,18 @@
-mod viability;
+mod dynamics;
+mod envelope;
 mod lyapunov;
+mod viability;
 
+pub use dynamics::{
+    check_reachability, AssetDynamics, DynamicsError, FlowProfile, ReachabilityExit,
+    ReachabilityReport, TrajectoryPoint, MAX_REACHABILITY_STEPS,
+};
+pub use envelope::{
+    AssetClass, Bound, BoundSide, EnvelopeError, EnvelopeViolation, ViabilityCheck,
+    ViabilityDimension, ViabilityEnvelope,
+};
+pub use lyapunov::{
+    CertifyError, LyapunovCertifier, LyapunovResidual, LyapunovViolation, QuadraticForm,
+    StabilityCertificate,
+};
 pub use viability::{ViabilityKernel, ViabilityState};
-pub use lyapunov::LyapunovResidual;
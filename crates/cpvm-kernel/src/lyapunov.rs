@@ -1,4 +1,7 @@
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::ViabilityState;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct LyapunovResidual {
@@ -15,3 +18,211 @@ impl LyapunovResidual {
         next.v_k <= self.v_k
     }
 }
+
+#[derive(Debug, Error, PartialEq)]
+pub enum CertifyError {
+    #[error("P must be symmetric")]
+    NotSymmetric,
+    #[error("P must be positive definite")]
+    NotPositiveDefinite,
+    #[error("alpha must lie in [0, 1], got {0}")]
+    InvalidAlpha(f64),
+    #[error("tolerance must be finite and non-negative, got {0}")]
+    InvalidTolerance(f64),
+    #[error("trajectory needs at least two states")]
+    TrajectoryTooShort,
+    #[error("state {0} is not finite")]
+    NonFiniteState(usize),
+}
+
+/// V(x) = (x - x*)ᵀ P (x - x*) over `ViabilityState::as_vector` dimensions.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct QuadraticForm {
+    pub p: [[f64; 3]; 3],
+    /// Equilibrium x*; zero gives V(x) = xᵀPx.
+    pub equilibrium: [f64; 3],
+}
+
+impl QuadraticForm {
+    /// Validate that P is symmetric positive definite (Sylvester's criterion).
+    pub fn new(p: [[f64; 3]; 3], equilibrium: [f64; 3]) -> Result<Self, CertifyError> {
+        for (i, row) in p.iter().enumerate() {
+            for (j, value) in row.iter().enumerate() {
+                if !value.is_finite() || (value - p[j][i]).abs() > 1e-12 {
+                    return Err(CertifyError::NotSymmetric);
+                }
+            }
+        }
+        let minor1 = p[0][0];
+        let minor2 = p[0][0] * p[1][1] - p[0][1] * p[1][0];
+        let minor3 = p[0][0] * (p[1][1] * p[2][2] - p[1][2] * p[2][1])
+            - p[0][1] * (p[1][0] * p[2][2] - p[1][2] * p[2][0])
+            + p[0][2] * (p[1][0] * p[2][1] - p[1][1] * p[2][0]);
+        if minor1 <= 0.0 || minor2 <= 0.0 || minor3 <= 0.0 {
+            return Err(CertifyError::NotPositiveDefinite);
+        }
+        Ok(Self { p, equilibrium })
+    }
+
+    /// Diagonal P, i.e. independently weighted squared deviations.
+    pub fn diagonal(weights: [f64; 3], equilibrium: [f64; 3]) -> Result<Self, CertifyError> {
+        let mut p = [[0.0; 3]; 3];
+        for (i, w) in weights.into_iter().enumerate() {
+            p[i][i] = w;
+        }
+        Self::new(p, equilibrium)
+    }
+
+    pub fn value(&self, state: &ViabilityState) -> f64 {
+        let x = state.as_vector();
+        let d: [f64; 3] = std::array::from_fn(|i| x[i] - self.equilibrium[i]);
+        (0..3)
+            .map(|i| (0..3).map(|j| d[i] * self.p[i][j] * d[j]).sum::<f64>())
+            .sum()
+    }
+}
+
+/// First step where ΔV exceeded −αV + tolerance.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct LyapunovViolation {
+    pub step: usize,
+    pub v_k: f64,
+    pub v_next: f64,
+    /// Largest ΔV the condition allowed at this step.
+    pub allowed_delta: f64,
+}
+
+/// Result of certifying one trajectory; serializable so schedulers and the
+/// reconciliation engine can attach it to the decisions it backs.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct StabilityCertificate {
+    pub kind: String,
+    pub form: QuadraticForm,
+    pub alpha: f64,
+    pub tolerance: f64,
+    /// V at every state of the trajectory.
+    pub values: Vec<f64>,
+    pub first_violation: Option<LyapunovViolation>,
+}
+
+impl StabilityCertificate {
+    pub const KIND: &'static str = "cpvm.lyapunov-certificate.v1";
+
+    pub fn is_certified(&self) -> bool {
+        self.first_violation.is_none()
+    }
+
+    /// JSON form for artifact lists such as `non_actuating_artifacts`.
+    pub fn to_artifact(&self) -> String {
+        serde_json::to_string(self).expect("certificate serializes")
+    }
+}
+
+/// Checks ΔV ≤ −αV (+ tolerance) at every step of a trajectory.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LyapunovCertifier {
+    pub form: QuadraticForm,
+    pub alpha: f64,
+    pub tolerance: f64,
+}
+
+impl LyapunovCertifier {
+    pub fn new(form: QuadraticForm, alpha: f64, tolerance: f64) -> Result<Self, CertifyError> {
+        if !(0.0..=1.0).contains(&alpha) {
+            return Err(CertifyError::InvalidAlpha(alpha));
+        }
+        if !tolerance.is_finite() || tolerance < 0.0 {
+            return Err(CertifyError::InvalidTolerance(tolerance));
+        }
+        Ok(Self {
+            form,
+            alpha,
+            tolerance,
+        })
+    }
+
+    pub fn certify(&self, states: &[ViabilityState]) -> Result<StabilityCertificate, CertifyError> {
+        if states.len() < 2 {
+            return Err(CertifyError::TrajectoryTooShort);
+        }
+        if let Some(i) = states
+            .iter()
+            .position(|s| s.as_vector().iter().any(|x| !x.is_finite()))
+        {
+            return Err(CertifyError::NonFiniteState(i));
+        }
+        let values: Vec<f64> = states.iter().map(|s| self.form.value(s)).collect();
+        let first_violation = values.windows(2).enumerate().find_map(|(step, w)| {
+            let allowed_delta = -self.alpha * w[0] + self.tolerance;
+            (w[1] - w[0] > allowed_delta).then_some(LyapunovViolation {
+                step,
+                v_k: w[0],
+                v_next: w[1],
+                allowed_delta,
+            })
+        });
+        Ok(StabilityCertificate {
+            kind: StabilityCertificate::KIND.to_string(),
+            form: self.form.clone(),
+            alpha: self.alpha,
+            tolerance: self.tolerance,
+            values,
+            first_violation,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(load: f64, temp: f64) -> ViabilityState {
+        ViabilityState {
+            load_fraction: load,
+            vibration_index: 0.0,
+            temperature_c: temp,
+        }
+    }
+
+    fn certifier() -> LyapunovCertifier {
+        let form = QuadraticForm::diagonal([1.0, 1.0, 0.01], [0.5, 0.0, 25.0]).unwrap();
+        LyapunovCertifier::new(form, 0.2, 1e-9).unwrap()
+    }
+
+    #[test]
+    fn converging_trajectory_is_certified() {
+        let states = [state(0.9, 35.0), state(0.7, 30.0), state(0.6, 27.0)];
+        let cert = certifier().certify(&states).unwrap();
+        assert!(cert.is_certified());
+        assert_eq!(cert.values.len(), 3);
+        let back: StabilityCertificate = serde_json::from_str(&cert.to_artifact()).unwrap();
+        assert_eq!(back.kind, StabilityCertificate::KIND);
+        assert!(back.is_certified());
+    }
+
+    #[test]
+    fn finds_first_violating_step() {
+        let states = [
+            state(0.9, 35.0),
+            state(0.7, 30.0),
+            state(0.69, 29.9),
+            state(0.9, 40.0),
+        ];
+        let cert = certifier().certify(&states).unwrap();
+        assert_eq!(cert.first_violation.unwrap().step, 1);
+    }
+
+    #[test]
+    fn rejects_indefinite_forms() {
+        assert_eq!(
+            QuadraticForm::diagonal([1.0, -1.0, 1.0], [0.0; 3]),
+            Err(CertifyError::NotPositiveDefinite)
+        );
+        let mut p = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        p[0][1] = 0.5;
+        assert_eq!(
+            QuadraticForm::new(p, [0.0; 3]),
+            Err(CertifyError::NotSymmetric)
+        );
+    }
+}
@@ -1,18 +1,30 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+use crate::{ViabilityCheck, ViabilityEnvelope};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct ViabilityState {
     pub load_fraction: f64,
     pub vibration_index: f64,
     pub temperature_c: f64,
 }
 
+impl ViabilityState {
+    /// `[load_fraction, vibration_index, temperature_c]`.
+    pub fn as_vector(&self) -> [f64; 3] {
+        [self.load_fraction, self.vibration_index, self.temperature_c]
+    }
+}
+
 pub struct ViabilityKernel;
 
 impl ViabilityKernel {
+    /// Check against [`ViabilityEnvelope::default`].
     pub fn is_within_envelope(state: &ViabilityState) -> bool {
-        state.load_fraction <= 1.0
-            && state.vibration_index <= 1.0
-            && (0.0..=60.0).contains(&state.temperature_c)
+        Self::check(&ViabilityEnvelope::default(), state).is_viable()
+    }
+
+    pub fn check(envelope: &ViabilityEnvelope, state: &ViabilityState) -> ViabilityCheck {
+        envelope.check(state)
     }
 }
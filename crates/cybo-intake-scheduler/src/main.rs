@@ -1,8 +1,10 @@
-mod series;
 mod optimizer;
+mod series;
 
 use anyhow::Result;
-use cpvm-kernel::ViabilityState;
+use ceim_kernel::AggregationConfig;
+use chrono::Utc;
+use cpvm_kernel::{AssetClass, AssetDynamics, ViabilityEnvelope, ViabilityState};
 
 use optimizer::optimize;
 use series::TimeSeriesPoint;
@@ -35,11 +37,23 @@ fn main() -> Result<()> {
         temperature_c: 30.0,
     };
 
-    if let Some(plan) = optimize(&series, &viability)? {
+    let envelope = ViabilityEnvelope::preset(AssetClass::Intake);
+    let plan = optimize(
+        &series,
+        &viability,
+        &envelope,
+        &AssetDynamics::default(),
+        &AggregationConfig::default(),
+        Utc::now().date_naive(),
+    )?;
+    if let Some(plan) = plan {
         println!(
-            "Intake {}-{}, K_n(TDS)={:.3}, K_n(nitrate)={:.3}",
-            plan.start_hour, plan.end_hour, plan.k_n_tds, plan.k_n_nitrate
+            "Intake {}-{}, K_n(TDS)={:.3}, K_n(nitrate)={:.3}, ecoimpact={:.3}",
+            plan.start_hour, plan.end_hour, plan.k_n_tds, plan.k_n_nitrate, plan.ecoimpact_score
         );
+        if let Some(cert) = &plan.stability {
+            println!("Lyapunov certificate: {}", cert.to_artifact());
+        }
     } else {
         println!("No viable intake window within CPVM envelope");
     }
@@ -1,6 +1,12 @@
 use anyhow::Result;
-use ceim-kernel::{CeimKernel, RegulatoryLimits, TimeSample};
-use cpvm-kernel::{ViabilityKernel, ViabilityState};
+use ceim_kernel::{
+    AggregationConfig, CeimKernel, ContaminantSeries, LimitsCatalog, MassLoadOptions, TimeSample,
+};
+use chrono::NaiveDate;
+use cpvm_kernel::{
+    check_reachability, AssetDynamics, FlowProfile, LyapunovCertifier, QuadraticForm,
+    StabilityCertificate, ViabilityEnvelope, ViabilityState,
+};
 
 use crate::series::TimeSeriesPoint;
 
@@ -9,74 +15,201 @@ pub struct IntakePlan {
     pub end_hour: u32,
     pub k_n_tds: f64,
     pub k_n_nitrate: f64,
+    /// Node aggregate over both contaminants; windows are ranked by this.
+    pub ecoimpact_score: f64,
+    /// Lyapunov certificate for the projected asset trajectory, measured
+    /// against the steady state at the window's closing flow.
+    pub stability: Option<StabilityCertificate>,
 }
 
+/// Projection step for the reachability check.
+const PROJECTION_STEP_H: f64 = 0.25;
+
+fn certify(
+    dynamics: &AssetDynamics,
+    states: &[ViabilityState],
+    closing_flow_q: f64,
+) -> Option<StabilityCertificate> {
+    let equilibrium = dynamics.steady_state(closing_flow_q).as_vector();
+    let form = QuadraticForm::diagonal([1.0, 1.0, 1.0 / 400.0], equilibrium).ok()?;
+    LyapunovCertifier::new(form, 0.0, 1e-9)
+        .ok()?
+        .certify(states)
+        .ok()
+}
+
+/// Pick the best-scoring window the asset can run without leaving the
+/// envelope. `viability` is the asset state at the first point's hour; each
+/// window is projected from the state the previous window ends in. A window
+/// whose flow or concentrations cannot be evaluated is skipped and the state
+/// is carried across it unchanged. Windows are scored against the limits in
+/// force on `on`.
 pub fn optimize(
     series: &[TimeSeriesPoint],
     viability: &ViabilityState,
+    envelope: &ViabilityEnvelope,
+    dynamics: &AssetDynamics,
+    weights: &AggregationConfig,
+    on: NaiveDate,
 ) -> Result<Option<IntakePlan>> {
-    if !ViabilityKernel::is_within_envelope(viability) {
+    if !envelope.check(viability).is_viable() {
         return Ok(None);
     }
 
+    let catalog = LimitsCatalog::standard();
+    let mut state = viability.clone();
     let mut best: Option<IntakePlan> = None;
     for window in series.windows(2) {
         let a = &window[0];
         let b = &window[1];
 
-        let samples_tds = vec![
-            TimeSample {
-                t_hours: a.hour as f64,
-                c_in: a.tds,
-                c_out: 0.0,
-                flow_q: a.flow_q,
-            },
-            TimeSample {
-                t_hours: b.hour as f64,
-                c_in: b.tds,
-                c_out: 0.0,
-                flow_q: b.flow_q,
-            },
-        ];
-        let samples_nitrate = vec![
-            TimeSample {
-                t_hours: a.hour as f64,
-                c_in: a.nitrate,
-                c_out: 0.0,
-                flow_q: a.flow_q,
-            },
-            TimeSample {
-                t_hours: b.hour as f64,
-                c_in: b.nitrate,
-                c_out: 0.0,
-                flow_q: b.flow_q,
-            },
-        ];
-        let limits = RegulatoryLimits {
-            epa: Some(1.0),
-            eu: Some(1.0),
-            who: Some(1.0),
+        // Constraint: the asset must stay viable for the whole window, not
+        // just at its start.
+        let duration = b.hour.saturating_sub(a.hour) as f64;
+        let Ok(profile) = FlowProfile::new(vec![(0.0, a.flow_q), (duration, b.flow_q)]) else {
+            continue;
         };
-        let tds_impact = CeimKernel::compute("TDS", 1.0, &samples_tds, &limits);
-        let nitrate_impact = CeimKernel::compute("nitrate", 1.0, &samples_nitrate, &limits);
+        let reach = check_reachability(envelope, dynamics, &state, &profile, PROJECTION_STEP_H)?;
+        state = reach.trajectory.last().expect("non-empty").state.clone();
+        if !reach.stays_viable() {
+            continue;
+        }
+
+        let sample = |p: &TimeSeriesPoint, c_in: f64| TimeSample {
+            t_hours: p.hour as f64,
+            c_in,
+            c_out: 0.0,
+            flow_q: p.flow_q,
+        };
+        let node = [
+            ("tds", [sample(a, a.tds), sample(b, b.tds)]),
+            ("nitrate", [sample(a, a.nitrate), sample(b, b.nitrate)]),
+        ]
+        .map(|(contaminant, samples)| ContaminantSeries {
+            contaminant: contaminant.to_string(),
+            samples: samples.iter().map(Into::into).collect(),
+            limits: catalog.limits_for(contaminant, on),
+        });
+        let Ok(aggregate) = CeimKernel::aggregate(&node, weights, &MassLoadOptions::default())
+        else {
+            continue;
+        };
+        let k_n = |c: &str| aggregate.impact(c).map_or(0.0, |i| i.k_n);
 
         let candidate = IntakePlan {
             start_hour: a.hour,
             end_hour: b.hour,
-            k_n_tds: tds_impact.k_n,
-            k_n_nitrate: nitrate_impact.k_n,
+            k_n_tds: k_n("tds"),
+            k_n_nitrate: k_n("nitrate"),
+            ecoimpact_score: aggregate.total_k_n,
+            stability: certify(dynamics, &reach.states(), b.flow_q),
         };
 
-        let better = match &best {
-            None => true,
-            Some(bst) => {
-                (candidate.k_n_tds + candidate.k_n_nitrate)
-                    > (bst.k_n_tds + bst.k_n_nitrate)
-            }
-        };
+        let better = best
+            .as_ref()
+            .is_none_or(|bst| candidate.ecoimpact_score > bst.ecoimpact_score);
         if better {
             best = Some(candidate);
         }
     }
     Ok(best)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cpvm_kernel::AssetClass;
+
+    fn on() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 6, 1).unwrap()
+    }
+
+    fn point(hour: u32, flow_q: f64, tds: f64) -> TimeSeriesPoint {
+        TimeSeriesPoint {
+            hour,
+            tds,
+            nitrate: 5.0,
+            flow_q,
+        }
+    }
+
+    #[test]
+    fn skips_windows_that_leave_the_envelope() {
+        let viability = ViabilityState {
+            load_fraction: 0.5,
+            vibration_index: 0.3,
+            temperature_c: 25.0,
+        };
+        let envelope = ViabilityEnvelope::preset(AssetClass::Intake);
+        // The first window scores higher but overloads the asset mid-window.
+        let series = [
+            point(0, 1.6, 900.0),
+            point(6, 0.8, 100.0),
+            point(12, 0.8, 100.0),
+        ];
+        let plan = optimize(
+            &series,
+            &viability,
+            &envelope,
+            &AssetDynamics::default(),
+            &AggregationConfig::default(),
+            on(),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!((plan.start_hour, plan.end_hour), (6, 12));
+        assert!(plan.stability.unwrap().is_certified());
+    }
+
+    #[test]
+    fn projects_each_window_from_the_previous_one() {
+        let viability = ViabilityState {
+            load_fraction: 0.8,
+            vibration_index: 0.48,
+            temperature_c: 20.0,
+        };
+        let envelope = ViabilityEnvelope::from_json(
+            r#"{"preset": "intake", "bounds": {"temperature_c": {"max": 30}}}"#,
+        )
+        .unwrap();
+        // From a cold start either window stays below 30 °C, but the second
+        // one starts where the first left the asset and overheats.
+        let series = [
+            point(0, 0.8, 100.0),
+            point(2, 0.8, 100.0),
+            point(4, 0.8, 900.0),
+        ];
+        let plan = |series: &[TimeSeriesPoint]| {
+            optimize(
+                series,
+                &viability,
+                &envelope,
+                &AssetDynamics::default(),
+                &AggregationConfig::default(),
+                on(),
+            )
+            .unwrap()
+        };
+        let chosen = plan(&series).unwrap();
+        assert_eq!((chosen.start_hour, chosen.end_hour), (0, 2));
+        assert_eq!(
+            plan(&series[1..]).map(|p| (p.start_hour, p.end_hour)),
+            Some((2, 4))
+        );
+
+        // A window with an unusable flow reading is skipped, not fatal.
+        let gappy = [
+            point(0, 0.8, 100.0),
+            point(2, f64::NAN, 100.0),
+            point(4, 0.8, 900.0),
+        ];
+        assert!(plan(&gappy).is_none());
+        let gappy = [
+            point(0, f64::NAN, 900.0),
+            point(2, 0.8, 100.0),
+            point(4, 0.8, 100.0),
+        ];
+        let chosen = plan(&gappy).unwrap();
+        assert_eq!((chosen.start_hour, chosen.end_hour), (2, 4));
+    }
+}
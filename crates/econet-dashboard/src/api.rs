@@ -1,33 +1,383 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail};
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
 use axum::{routing::get, Json, Router};
-use serde::Serialize;
+use ceim_shard_schema::CeimNodeState;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use snc_eco_corridor::{CorridorProfile, CorridorRegistry};
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt};
+
+use crate::banding::Banding;
+use crate::config::Config;
+use crate::live::{self, LiveShards};
+use crate::storage::{load_latest_shard, node_history, open_store};
 
-use crate::storage::{band_for_score, load_latest_shard, EcoNode};
+const DEFAULT_PAGE_SIZE: usize = 100;
+const MAX_PAGE_SIZE: usize = 1000;
+
+pub struct AppState {
+    config: Config,
+    /// Configured corridors resolved against the corridor shards.
+    corridors: Vec<(CorridorProfile, Vec<String>)>,
+    live: Arc<LiveShards>,
+}
+
+impl AppState {
+    pub fn new(config: Config) -> anyhow::Result<Self> {
+        config.banding.validate()?;
+        let registry = match &config.corridor_shards {
+            Some(root) => CorridorRegistry::load_dir(root)?,
+            None if config.corridors.is_empty() => CorridorRegistry::new(),
+            None => bail!("`corridors` are configured but `corridor_shards` is not"),
+        };
+        let corridors = config
+            .corridors
+            .iter()
+            .map(|c| {
+                let profile = registry
+                    .latest(&c.corridor)
+                    .ok_or_else(|| anyhow!("unknown corridor {}", c.corridor))?;
+                Ok((profile.clone(), c.nodes.clone()))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        let live = Arc::new(LiveShards::new(load_latest_shard(&config.data_dir)?));
+        Ok(Self {
+            config,
+            corridors,
+            live,
+        })
+    }
+
+    /// Keep the shard cache current and feed `/events` subscribers.
+    pub fn spawn_watch(&self) {
+        tokio::spawn(live::watch(
+            self.live.clone(),
+            self.config.data_dir.clone(),
+            self.config.banding.clone(),
+            Duration::from_millis(self.config.watch_interval_ms),
+        ));
+    }
+
+    fn latest_nodes(&self) -> Vec<CeimNodeState> {
+        self.live
+            .latest()
+            .map(|s| s.nodes.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Any failure to read the store is a 500 with the error message.
+struct ApiError(anyhow::Error);
+
+impl<E: Into<anyhow::Error>> From<E> for ApiError {
+    fn from(e: E) -> Self {
+        Self(e.into())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (StatusCode::INTERNAL_SERVER_ERROR, format!("{:#}", self.0)).into_response()
+    }
+}
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
 
 #[derive(Serialize)]
 struct NodeView {
     node_id: String,
     contaminant: String,
     k_n: f64,
-    ecoimpact_band: f64,
-}
-
-async fn list_nodes() -> Json<Vec<NodeView>> {
-    let shard = load_latest_shard("data/ceim").ok().flatten();
-    let mut out = Vec::new();
-    if let Some(s) = shard {
-        for n in s.nodes {
-            let band = band_for_score(n.k_n);
-            out.push(NodeView {
-                node_id: n.node_id,
-                contaminant: n.contaminant,
-                k_n: n.k_n,
-                ecoimpact_band: band,
-            });
+    ecoimpact_score: f64,
+    /// The band fields are `None` for unregulated contaminants and for
+    /// contaminants without a configured reference.
+    k_n_norm: Option<f64>,
+    ecoimpact_band: Option<f64>,
+    ecoimpact_band_label: Option<String>,
+    binding_authority: Option<String>,
+    unregulated: bool,
+}
+
+impl NodeView {
+    fn new(n: CeimNodeState, banding: &Banding) -> Self {
+        let classified = banding.classify(&n);
+        Self {
+            k_n_norm: classified.map(|(norm, _)| norm),
+            ecoimpact_band: classified.map(|(_, band)| band.value),
+            ecoimpact_band_label: classified.map(|(_, band)| band.label.clone()),
+            node_id: n.node_id,
+            contaminant: n.contaminant,
+            k_n: n.k_n,
+            ecoimpact_score: n.ecoimpact_score,
+            binding_authority: n.binding_authority,
+            unregulated: n.unregulated,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Page<T> {
+    total: usize,
+    offset: usize,
+    limit: usize,
+    items: Vec<T>,
+}
+
+impl<T> Page<T> {
+    fn new(items: Vec<T>, offset: Option<usize>, limit: Option<usize>) -> Self {
+        let offset = offset.unwrap_or(0);
+        let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
+        Self {
+            total: items.len(),
+            offset,
+            limit,
+            items: items.into_iter().skip(offset).take(limit).collect(),
         }
     }
-    Json(out)
 }
 
-pub fn app() -> Router {
-    Router::new().route("/nodes", get(list_nodes))
+/// Filters shared by `/nodes` and `/nodes/:id/history`.
+struct NodeFilter {
+    contaminant: Option<String>,
+    /// Band label, or its numeric value.
+    band: Option<String>,
+}
+
+impl NodeFilter {
+    fn matches(&self, view: &NodeView) -> bool {
+        self.contaminant
+            .as_ref()
+            .is_none_or(|c| c.eq_ignore_ascii_case(&view.contaminant))
+            && self.band.as_ref().is_none_or(|b| {
+                view.ecoimpact_band_label
+                    .as_ref()
+                    .is_some_and(|label| label.eq_ignore_ascii_case(b))
+                    || b.parse()
+                        .ok()
+                        .is_some_and(|v: f64| view.ecoimpact_band == Some(v))
+            })
+    }
+}
+
+// serde_urlencoded cannot parse numbers through `#[serde(flatten)]`, so the
+// query structs repeat the filter and paging fields.
+#[derive(Deserialize)]
+struct NodesQuery {
+    contaminant: Option<String>,
+    band: Option<String>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct HistoryQuery {
+    /// Inclusive.
+    from: Option<DateTime<Utc>>,
+    /// Exclusive.
+    to: Option<DateTime<Utc>>,
+    contaminant: Option<String>,
+    band: Option<String>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+}
+
+async fn list_nodes(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<NodesQuery>,
+) -> ApiResult<Page<NodeView>> {
+    let filter = NodeFilter {
+        contaminant: q.contaminant,
+        band: q.band,
+    };
+    let views = state
+        .latest_nodes()
+        .into_iter()
+        .map(|n| NodeView::new(n, &state.config.banding))
+        .filter(|v| filter.matches(v))
+        .collect();
+    Ok(Json(Page::new(views, q.offset, q.limit)))
+}
+
+#[derive(Serialize)]
+struct HistoryPoint {
+    generated_at: DateTime<Utc>,
+    #[serde(flatten)]
+    node: NodeView,
+}
+
+async fn node_history_handler(
+    State(state): State<Arc<AppState>>,
+    Path(node_id): Path<String>,
+    Query(q): Query<HistoryQuery>,
+) -> ApiResult<Page<HistoryPoint>> {
+    let filter = NodeFilter {
+        contaminant: q.contaminant,
+        band: q.band,
+    };
+    let points = node_history(&state.config.data_dir, &node_id, q.from, q.to)?
+        .into_iter()
+        .map(|(generated_at, n)| HistoryPoint {
+            generated_at,
+            node: NodeView::new(n, &state.config.banding),
+        })
+        .filter(|p| filter.matches(&p.node))
+        .collect();
+    Ok(Json(Page::new(points, q.offset, q.limit)))
+}
+
+#[derive(Serialize)]
+struct CorridorView {
+    corridor: CorridorProfile,
+    nodes: Vec<NodeView>,
+    /// Highest ecoimpact score among the corridor's nodes in the latest shard.
+    ecoimpact_score: Option<f64>,
+}
+
+async fn list_corridors(State(state): State<Arc<AppState>>) -> ApiResult<Vec<CorridorView>> {
+    let nodes = state.latest_nodes();
+    let views = state
+        .corridors
+        .iter()
+        .map(|(profile, node_ids)| {
+            let nodes: Vec<NodeView> = nodes
+                .iter()
+                .filter(|n| node_ids.contains(&n.node_id))
+                .map(|n| NodeView::new(n.clone(), &state.config.banding))
+                .collect();
+            let ecoimpact_score = nodes.iter().map(|n| n.ecoimpact_score).reduce(f64::max);
+            CorridorView {
+                corridor: profile.clone(),
+                nodes,
+                ecoimpact_score,
+            }
+        })
+        .collect();
+    Ok(Json(views))
+}
+
+#[derive(Serialize)]
+struct Health {
+    /// `ok`, `stale` or `empty`; anything but `ok` is served as 503.
+    status: &'static str,
+    shards: usize,
+    latest_shard: Option<DateTime<Utc>>,
+    age_seconds: Option<i64>,
+}
+
+async fn healthz(State(state): State<Arc<AppState>>) -> Result<Response, ApiError> {
+    let store = open_store(&state.config.data_dir)?;
+    let latest = store.entries().last().map(|e| e.generated_at);
+    let age_seconds = latest.map(|t| (Utc::now() - t).num_seconds());
+    let status = match age_seconds {
+        None => "empty",
+        Some(age) if age > state.config.max_shard_age_seconds => "stale",
+        Some(_) => "ok",
+    };
+    let code = if status == "ok" {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    let health = Health {
+        status,
+        shards: store.entries().len(),
+        latest_shard: latest,
+        age_seconds,
+    };
+    Ok((code, Json(health)).into_response())
+}
+
+/// `shard` events carry a `ShardEvent`; `lagged` means the subscriber missed
+/// that many events and should refetch `/nodes`.
+async fn events(
+    State(state): State<Arc<AppState>>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let stream = BroadcastStream::new(state.live.subscribe()).map(|msg| match msg {
+        Ok(event) => Event::default().event("shard").json_data(&*event),
+        Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+            Ok(Event::default().event("lagged").data(skipped.to_string()))
+        }
+    });
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+pub fn app(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/nodes", get(list_nodes))
+        .route("/nodes/:id/history", get(node_history_handler))
+        .route("/corridors", get(list_corridors))
+        .route("/healthz", get(healthz))
+        .route("/events", get(events))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn view(contaminant: &str, k_n: f64) -> NodeView {
+        let node = CeimNodeState {
+            node_id: "n1".into(),
+            contaminant: contaminant.into(),
+            k_n,
+            mass_load_g: None,
+            binding_authority: Some("epa".into()),
+            unregulated: false,
+            ecoimpact_score: k_n,
+            window: None,
+            last_updated: Utc::now(),
+        };
+        let mut banding = Banding::default();
+        banding
+            .normalization
+            .reference_k_n
+            .extend([("pfoa".into(), 1.0), ("nitrate".into(), 1.0)]);
+        NodeView::new(node, &banding)
+    }
+
+    #[test]
+    fn filters_then_pages() {
+        let filter = NodeFilter {
+            contaminant: Some("PFOA".into()),
+            band: Some("High".into()),
+        };
+        assert!(filter.matches(&view("pfoa", 2.0)));
+        assert_eq!(view("pfoa", 2.0).k_n_norm, Some(1.0));
+        assert!(!filter.matches(&view("pfoa", 0.1)));
+        assert!(!filter.matches(&view("nitrate", 2.0)));
+        let by_value = NodeFilter {
+            contaminant: None,
+            band: Some("0.5".into()),
+        };
+        assert!(by_value.matches(&view("nitrate", 0.5)));
+
+        let page = Page::new((0..250).collect(), Some(240), None);
+        assert_eq!((page.total, page.limit), (250, DEFAULT_PAGE_SIZE));
+        assert_eq!(page.items, (240..250).collect::<Vec<_>>());
+        assert_eq!(Page::new(vec![1], None, Some(5000)).limit, MAX_PAGE_SIZE);
+    }
+
+    #[test]
+    fn unknown_corridor_is_a_startup_error() {
+        let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../qpudatashards");
+        let mut config = Config {
+            corridor_shards: Some(root),
+            ..Config::default()
+        };
+        config.corridors.push(crate::config::CorridorNodes {
+            corridor: "tribal.gric-epa-2024".into(),
+            nodes: vec!["n1".into()],
+        });
+        assert_eq!(AppState::new(config.clone()).unwrap().corridors.len(), 1);
+        config.corridors[0].corridor = "city.nowhere-2024".into();
+        assert!(AppState::new(config).is_err());
+    }
 }
@@ -0,0 +1,214 @@
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Result};
+use ceim_shard_schema::CeimNodeState;
+use serde::{Deserialize, Serialize};
+
+/// Maps raw K_n onto 0–1 before banding. Raw K_n is a mass over a limit
+/// concentration, so it has no fixed scale; every contaminant needs an
+/// explicit reference and those without one are left unbanded.
+#[derive(Debug, Deserialize, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct Normalization {
+    /// Per-contaminant K_n that normalises to 1.0.
+    pub reference_k_n: BTreeMap<String, f64>,
+    /// Per-contaminant reference mass load in grams; a node at that load
+    /// normalises to 1.0. Takes precedence over `reference_k_n`.
+    pub reference_load_g: BTreeMap<String, f64>,
+}
+
+fn reference_for(references: &BTreeMap<String, f64>, contaminant: &str) -> Option<f64> {
+    references
+        .iter()
+        .find(|(c, _)| c.eq_ignore_ascii_case(contaminant))
+        .map(|(_, &reference)| reference)
+}
+
+impl Normalization {
+    /// Normalised K_n clamped to 0–1; `None` for unregulated contaminants
+    /// and for contaminants without a reference.
+    pub fn normalize(&self, node: &CeimNodeState) -> Option<f64> {
+        if node.unregulated {
+            return None;
+        }
+        let reference_load = reference_for(&self.reference_load_g, &node.contaminant);
+        let ratio = match (reference_load, node.mass_load_g) {
+            (Some(reference), Some(load)) => load / reference,
+            _ => node.k_n / reference_for(&self.reference_k_n, &node.contaminant)?,
+        };
+        Some(if ratio.is_nan() {
+            0.0
+        } else {
+            ratio.clamp(0.0, 1.0)
+        })
+    }
+
+    fn validate(&self) -> Result<()> {
+        let positive = |v: f64| v.is_finite() && v > 0.0;
+        if let Some((c, _)) = self.reference_k_n.iter().find(|(_, &v)| !positive(v)) {
+            bail!("reference K_n for {c} must be positive");
+        }
+        if let Some((c, _)) = self.reference_load_g.iter().find(|(_, &v)| !positive(v)) {
+            bail!("reference load for {c} must be positive");
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Band {
+    pub label: String,
+    /// Exclusive upper bound on normalised K_n; `None` for the top band.
+    pub below: Option<f64>,
+    /// Numeric value reported for the band.
+    pub value: f64,
+}
+
+/// Bands in ascending order; the last one is open-ended.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(transparent)]
+pub struct BandScale(pub Vec<Band>);
+
+impl Default for BandScale {
+    fn default() -> Self {
+        let band = |label: &str, below, value| Band {
+            label: label.to_string(),
+            below,
+            value,
+        };
+        Self(vec![
+            band("low", Some(0.3), 0.1),
+            band("solid", Some(0.7), 0.5),
+            band("high", None, 0.9),
+        ])
+    }
+}
+
+impl BandScale {
+    pub fn band_for(&self, k_n_norm: f64) -> &Band {
+        self.0
+            .iter()
+            .find(|b| b.below.is_none_or(|below| k_n_norm < below))
+            .unwrap_or_else(|| &self.0[self.0.len() - 1])
+    }
+
+    fn validate(&self) -> Result<()> {
+        let Some((top, rest)) = self.0.split_last() else {
+            bail!("at least one band is required");
+        };
+        if top.below.is_some() {
+            bail!("the top band `{}` must not set `below`", top.label);
+        }
+        let mut previous = f64::NEG_INFINITY;
+        for band in rest {
+            match band.below {
+                Some(below) if below > previous => previous = below,
+                _ => bail!(
+                    "band `{}` needs a `below` above the previous band",
+                    band.label
+                ),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Normalisation plus band scale, as configured.
+#[derive(Debug, Deserialize, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct Banding {
+    pub normalization: Normalization,
+    pub bands: BandScale,
+}
+
+impl Banding {
+    pub fn validate(&self) -> Result<()> {
+        self.normalization.validate()?;
+        self.bands.validate()
+    }
+
+    /// Normalised K_n and its band; `None` for unregulated contaminants.
+    pub fn classify(&self, node: &CeimNodeState) -> Option<(f64, &Band)> {
+        let k_n_norm = self.normalization.normalize(node)?;
+        Some((k_n_norm, self.bands.band_for(k_n_norm)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Utc};
+
+    fn node(contaminant: &str, k_n: f64, mass_load_g: Option<f64>) -> CeimNodeState {
+        CeimNodeState {
+            node_id: "n1".into(),
+            contaminant: contaminant.into(),
+            k_n,
+            mass_load_g,
+            binding_authority: Some("epa".into()),
+            unregulated: false,
+            ecoimpact_score: k_n,
+            window: None,
+            last_updated: DateTime::<Utc>::UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn normalises_before_banding() {
+        let mut banding = Banding::default();
+        banding
+            .normalization
+            .reference_k_n
+            .extend([("PFOA".into(), 20.0), ("nitrate".into(), 20.0)]);
+        banding
+            .normalization
+            .reference_load_g
+            .insert("Nitrate".into(), 100.0);
+
+        let (norm, band) = banding.classify(&node("pfoa", 8.0, Some(1e-4))).unwrap();
+        assert_eq!((norm, band.label.as_str()), (0.4, "solid"));
+        let (norm, band) = banding
+            .classify(&node("nitrate", 40.0, Some(10.0)))
+            .unwrap();
+        assert_eq!((norm, band.value), (0.1, 0.1));
+        // Without a recorded load the reference load cannot apply.
+        assert_eq!(
+            banding.classify(&node("nitrate", 40.0, None)).unwrap().0,
+            1.0
+        );
+
+        let mut unregulated = node("novel", 0.0, Some(5.0));
+        unregulated.unregulated = true;
+        assert!(banding.classify(&unregulated).is_none());
+    }
+
+    #[test]
+    fn raw_k_n_is_not_banded_without_a_reference() {
+        // A typical raw K_n is far above 1; it must not default to "high".
+        let typical = node("pfoa", 4.0e5, Some(0.8));
+        assert!(Banding::default().classify(&typical).is_none());
+
+        let mut banding = Banding::default();
+        banding
+            .normalization
+            .reference_k_n
+            .insert("pfoa".into(), 2.0e6);
+        let (norm, band) = banding.classify(&typical).unwrap();
+        assert_eq!((norm, band.label.as_str()), (0.2, "low"));
+    }
+
+    #[test]
+    fn validates_scale() {
+        assert!(Banding::default().validate().is_ok());
+        let scale: BandScale = serde_json::from_str(
+            r#"[
+                {"label": "ok", "below": 0.5, "value": 0},
+                {"label": "watch", "below": 0.5, "value": 0.5},
+                {"label": "alert", "value": 1}
+            ]"#,
+        )
+        .unwrap();
+        assert!(scale.validate().is_err());
+        assert!(BandScale(Vec::new()).validate().is_err());
+    }
+}
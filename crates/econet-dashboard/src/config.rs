@@ -0,0 +1,117 @@
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+
+use crate::banding::Banding;
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct Config {
+    pub bind: SocketAddr,
+    /// Shard store written by phoenix-bridge.
+    pub data_dir: PathBuf,
+    /// Root of the corridor shards (`qpudatashards/`); `/corridors` is empty
+    /// without it.
+    pub corridor_shards: Option<PathBuf>,
+    pub corridors: Vec<CorridorNodes>,
+    /// `/healthz` reports stale once the newest shard is older than this.
+    pub max_shard_age_seconds: i64,
+    /// How often the shard store is checked for new shards to push to
+    /// `/events` subscribers.
+    pub watch_interval_ms: u64,
+    /// How K_n is normalised and banded for display.
+    pub banding: Banding,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            bind: SocketAddr::from(([0, 0, 0, 0], 8080)),
+            data_dir: PathBuf::from("data/ceim"),
+            corridor_shards: None,
+            corridors: Vec::new(),
+            max_shard_age_seconds: 900,
+            watch_interval_ms: 1000,
+            banding: Banding::default(),
+        }
+    }
+}
+
+/// Nodes monitored inside one corridor, by corridor code
+/// (e.g. `tribal.gric-epa-2024`).
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct CorridorNodes {
+    pub corridor: String,
+    pub nodes: Vec<String>,
+}
+
+impl Config {
+    /// `--config <file>` loads a JSON config; `--bind`, `--data-dir` and
+    /// `--corridor-shards` override it.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut args = args.into_iter();
+        let mut config_path = None;
+        let mut overrides = Vec::new();
+        while let Some(flag) = args.next() {
+            let value = args.next().ok_or_else(|| anyhow!("{flag} needs a value"))?;
+            match flag.as_str() {
+                "--config" => config_path = Some(PathBuf::from(value)),
+                "--bind" | "--data-dir" | "--corridor-shards" => overrides.push((flag, value)),
+                _ => bail!("unknown option {flag}"),
+            }
+        }
+
+        let mut cfg = match config_path {
+            Some(path) => {
+                let raw = std::fs::read_to_string(&path)
+                    .with_context(|| format!("{}", path.display()))?;
+                serde_json::from_str(&raw).with_context(|| format!("{}", path.display()))?
+            }
+            None => Config::default(),
+        };
+        for (flag, value) in overrides {
+            match flag.as_str() {
+                "--bind" => cfg.bind = value.parse().with_context(|| format!("--bind {value}"))?,
+                "--data-dir" => cfg.data_dir = value.into(),
+                _ => cfg.corridor_shards = Some(value.into()),
+            }
+        }
+        Ok(cfg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn flags_override_file() {
+        let path = std::env::temp_dir().join(format!("econet-config-{}.json", std::process::id()));
+        std::fs::write(
+            &path,
+            r#"{"data_dir": "/srv/ceim", "corridors": [{"corridor": "c1", "nodes": ["n1"]}]}"#,
+        )
+        .unwrap();
+        let cfg = Config::from_args(args(&[
+            "--config",
+            path.to_str().unwrap(),
+            "--bind",
+            "127.0.0.1:9000",
+        ]))
+        .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(cfg.bind, SocketAddr::from(([127, 0, 0, 1], 9000)));
+        assert_eq!(cfg.data_dir, PathBuf::from("/srv/ceim"));
+        assert_eq!(cfg.corridors[0].nodes, vec!["n1".to_string()]);
+        assert_eq!(cfg.max_shard_age_seconds, 900);
+        assert!(Config::from_args(args(&["--port"])).is_err());
+        assert!(Config::from_args(args(&["--bind", "nowhere"])).is_err());
+    }
+}
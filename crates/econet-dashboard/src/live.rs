@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use anyhow::Result;
+use ceim_shard_schema::{read_shard, CeimNodeState, CeimShard};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::broadcast;
+use tracing::warn;
+
+use crate::banding::Banding;
+use crate::storage::open_store;
+
+/// Events a slow subscriber may fall behind by before it is told to resync.
+const EVENT_BUFFER: usize = 64;
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct NodeDelta {
+    pub node_id: String,
+    pub contaminant: String,
+    pub k_n: f64,
+    /// `None` when the node/contaminant is new in this shard.
+    pub previous_k_n: Option<f64>,
+    pub delta_k_n: Option<f64>,
+    pub ecoimpact_score: f64,
+    pub band: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct BandAlert {
+    pub node_id: String,
+    pub contaminant: String,
+    pub from: String,
+    pub to: String,
+    /// `true` when the node moved into a higher band.
+    pub rising: bool,
+}
+
+/// What changed between two consecutive shards. Unchanged nodes are omitted.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct ShardEvent {
+    pub generated_at: DateTime<Utc>,
+    pub changes: Vec<NodeDelta>,
+    /// `(node_id, contaminant)` pairs missing from the new shard.
+    pub removed: Vec<(String, String)>,
+    pub alerts: Vec<BandAlert>,
+}
+
+/// Band index and label, for comparing bands by position in the scale.
+fn band_of(banding: &Banding, node: &CeimNodeState) -> Option<(usize, String)> {
+    let (_, band) = banding.classify(node)?;
+    let index = banding.bands.0.iter().position(|b| b == band)?;
+    Some((index, band.label.clone()))
+}
+
+pub fn diff(previous: Option<&CeimShard>, next: &CeimShard, banding: &Banding) -> ShardEvent {
+    let before: HashMap<(&str, &str), _> = previous
+        .map(|s| s.nodes.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .map(|n| ((n.node_id.as_str(), n.contaminant.as_str()), n))
+        .collect();
+
+    let mut changes = Vec::new();
+    let mut alerts = Vec::new();
+    for node in &next.nodes {
+        let old = before.get(&(node.node_id.as_str(), node.contaminant.as_str()));
+        let band = band_of(banding, node);
+        if let Some(old) = old {
+            if old.k_n == node.k_n && old.ecoimpact_score == node.ecoimpact_score {
+                continue;
+            }
+            if let (Some((from_idx, from)), Some((to_idx, to))) = (band_of(banding, old), &band) {
+                if from_idx != *to_idx {
+                    alerts.push(BandAlert {
+                        node_id: node.node_id.clone(),
+                        contaminant: node.contaminant.clone(),
+                        from,
+                        to: to.clone(),
+                        rising: *to_idx > from_idx,
+                    });
+                }
+            }
+        }
+        changes.push(NodeDelta {
+            node_id: node.node_id.clone(),
+            contaminant: node.contaminant.clone(),
+            k_n: node.k_n,
+            previous_k_n: old.map(|o| o.k_n),
+            delta_k_n: old.map(|o| node.k_n - o.k_n),
+            ecoimpact_score: node.ecoimpact_score,
+            band: band.map(|(_, label)| label),
+        });
+    }
+
+    let mut removed: Vec<(String, String)> = before
+        .keys()
+        .filter(|(id, c)| {
+            !next
+                .nodes
+                .iter()
+                .any(|n| n.node_id == *id && n.contaminant == *c)
+        })
+        .map(|(id, c)| (id.to_string(), c.to_string()))
+        .collect();
+    removed.sort();
+    ShardEvent {
+        generated_at: next.generated_at,
+        changes,
+        removed,
+        alerts,
+    }
+}
+
+/// The newest shard, cached in memory, and a broadcast of the changes each
+/// newer shard brings.
+pub struct LiveShards {
+    latest: RwLock<Option<Arc<CeimShard>>>,
+    events: broadcast::Sender<Arc<ShardEvent>>,
+}
+
+impl LiveShards {
+    pub fn new(initial: Option<CeimShard>) -> Self {
+        Self {
+            latest: RwLock::new(initial.map(Arc::new)),
+            events: broadcast::channel(EVENT_BUFFER).0,
+        }
+    }
+
+    pub fn latest(&self) -> Option<Arc<CeimShard>> {
+        self.latest
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<ShardEvent>> {
+        self.events.subscribe()
+    }
+
+    /// Cache `shard` and publish its changes, unless it is not newer than the
+    /// cached one.
+    pub fn update(&self, shard: CeimShard, banding: &Banding) -> Option<Arc<ShardEvent>> {
+        let mut latest = self.latest.write().unwrap_or_else(|e| e.into_inner());
+        if latest
+            .as_ref()
+            .is_some_and(|l| l.generated_at >= shard.generated_at)
+        {
+            return None;
+        }
+        let event = Arc::new(diff(latest.as_deref(), &shard, banding));
+        *latest = Some(Arc::new(shard));
+        // No subscribers is not an error.
+        let _ = self.events.send(event.clone());
+        Some(event)
+    }
+}
+
+/// Poll the store's manifest, which is rewritten after every shard, and
+/// publish each shard newer than the cached one. Unreadable shards are
+/// logged and skipped.
+pub async fn watch(live: Arc<LiveShards>, data_dir: PathBuf, banding: Banding, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        if let Err(e) = poll(&live, &data_dir, &banding) {
+            warn!("shard watch: {e:#}");
+        }
+    }
+}
+
+fn poll(live: &LiveShards, data_dir: &Path, banding: &Banding) -> Result<()> {
+    let store = open_store(data_dir)?;
+    let cached = live.latest().map(|s| s.generated_at);
+    for entry in store
+        .entries()
+        .iter()
+        .filter(|e| cached.is_none_or(|c| e.generated_at > c))
+    {
+        match read_shard(&store, entry) {
+            Ok(shard) => {
+                live.update(shard, banding);
+            }
+            Err(e) => warn!("skipping shard {}: {e}", entry.file),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ceim_shard_schema::{write_shard, SHARD_PREFIX};
+    use chrono::Duration;
+    use shard_store::ShardStore;
+
+    fn at(hours: i64) -> DateTime<Utc> {
+        DateTime::<Utc>::UNIX_EPOCH + Duration::hours(hours)
+    }
+
+    fn node(node_id: &str, k_n: f64) -> CeimNodeState {
+        CeimNodeState {
+            node_id: node_id.into(),
+            contaminant: "pfoa".into(),
+            k_n,
+            mass_load_g: None,
+            binding_authority: Some("epa".into()),
+            unregulated: false,
+            ecoimpact_score: k_n,
+            window: None,
+            last_updated: at(0),
+        }
+    }
+
+    fn banding() -> Banding {
+        let mut banding = Banding::default();
+        banding
+            .normalization
+            .reference_k_n
+            .insert("pfoa".into(), 1.0);
+        banding
+    }
+
+    #[test]
+    fn diff_reports_deltas_and_band_crossings() {
+        let banding = banding();
+        let before = CeimShard::new(
+            at(0),
+            vec![node("n1", 0.2), node("n2", 0.5), node("n3", 1.0)],
+        );
+        let after = CeimShard::new(
+            at(1),
+            vec![node("n1", 0.8), node("n2", 0.5), node("n4", 0.1)],
+        );
+        let event = diff(Some(&before), &after, &banding);
+
+        let changed: Vec<&str> = event.changes.iter().map(|d| d.node_id.as_str()).collect();
+        assert_eq!(changed, vec!["n1", "n4"]);
+        assert!((event.changes[0].delta_k_n.unwrap() - 0.6).abs() < 1e-12);
+        assert_eq!(event.changes[1].previous_k_n, None);
+        assert_eq!(event.removed, vec![("n3".to_string(), "pfoa".to_string())]);
+        assert_eq!(
+            event.alerts,
+            vec![BandAlert {
+                node_id: "n1".into(),
+                contaminant: "pfoa".into(),
+                from: "low".into(),
+                to: "high".into(),
+                rising: true,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn poll_caches_and_publishes_new_shards() {
+        let dir = std::env::temp_dir().join(format!("econet-live-{}", std::process::id()));
+        let mut store = ShardStore::open(&dir, SHARD_PREFIX).unwrap();
+        write_shard(&mut store, &CeimShard::new(at(0), vec![node("n1", 0.2)])).unwrap();
+
+        let banding = banding();
+        let live = LiveShards::new(None);
+        let mut events = live.subscribe();
+        poll(&live, &dir, &banding).unwrap();
+        write_shard(&mut store, &CeimShard::new(at(1), vec![node("n1", 0.9)])).unwrap();
+        poll(&live, &dir, &banding).unwrap();
+        poll(&live, &dir, &banding).unwrap();
+
+        assert_eq!(events.recv().await.unwrap().generated_at, at(0));
+        let second = events.recv().await.unwrap();
+        assert_eq!(second.alerts.len(), 1);
+        assert!(events.try_recv().is_err());
+        assert_eq!(live.latest().unwrap().generated_at, at(1));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn poll_skips_unreadable_shards() {
+        let dir = std::env::temp_dir().join(format!("econet-live-bad-{}", std::process::id()));
+        let mut store = ShardStore::open(&dir, SHARD_PREFIX).unwrap();
+        let mut entries = Vec::new();
+        for h in 0..3 {
+            let shard = CeimShard::new(at(h), vec![node("n1", 0.1 * h as f64)]);
+            entries.push(write_shard(&mut store, &shard).unwrap());
+        }
+        // The middle shard no longer matches its manifest digest.
+        std::fs::write(dir.join(&entries[1].file), b"{}").unwrap();
+
+        let live = LiveShards::new(None);
+        let mut events = live.subscribe();
+        poll(&live, &dir, &banding()).unwrap();
+
+        assert_eq!(events.recv().await.unwrap().generated_at, at(0));
+        assert_eq!(events.recv().await.unwrap().generated_at, at(2));
+        assert!(events.try_recv().is_err());
+        assert_eq!(live.latest().unwrap().generated_at, at(2));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
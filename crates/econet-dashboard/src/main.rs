@@ -1,23 +1,34 @@
 mod api;
+mod banding;
+mod config;
+mod live;
 mod storage;
 
-use std::net::SocketAddr;
+use std::sync::Arc;
 
-use axum::Router;
+use anyhow::Result;
 use tracing::info;
 use tracing_subscriber::EnvFilter;
 
+use config::Config;
+
 #[tokio::main]
-async fn main() {
+async fn main() -> Result<()> {
     tracing_subscriber::fmt()
         .with_env_filter(EnvFilter::from_default_env())
         .init();
 
-    let app: Router = api::app();
-    let addr = SocketAddr::from(([0, 0, 0, 0], 8080));
-    info!("EcoNet dashboard API listening on {}", addr);
-    axum::Server::bind(&addr)
-        .serve(app.into_make_service())
-        .await
-        .unwrap();
+    let cfg = Config::from_args(std::env::args().skip(1))?;
+    let addr = cfg.bind;
+    info!(
+        "EcoNet dashboard API listening on {}, shards in {}",
+        addr,
+        cfg.data_dir.display()
+    );
+    let state = Arc::new(api::AppState::new(cfg)?);
+    state.spawn_watch();
+    let app = api::app(state);
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, app).await?;
+    Ok(())
 }
@@ -1,45 +1,86 @@
-use std::fs;
-use std::path::PathBuf;
+use std::path::Path;
 
 use anyhow::Result;
-use serde::{Deserialize, Serialize};
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct EcoNode {
-    pub node_id: String,
-    pub contaminant: String,
-    pub k_n: f64,
-    pub ecoimpact_score: f64,
+use ceim_shard_schema::{read_shard, CeimNodeState, CeimShard, SHARD_PREFIX};
+use chrono::{DateTime, Utc};
+use shard_store::ShardStore;
+
+/// Open the store read-only; the dashboard never creates or rewrites it.
+pub fn open_store(dir: &Path) -> Result<ShardStore> {
+    Ok(ShardStore::open_existing(dir, SHARD_PREFIX)?)
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct EcoShard {
-    pub generated_at: String,
-    pub nodes: Vec<EcoNode>,
+/// Newest shard in the store at `dir`, migrated to the current schema.
+pub fn load_latest_shard(dir: &Path) -> Result<Option<CeimShard>> {
+    Ok(ceim_shard_schema::load_latest(dir)?)
 }
 
-pub fn load_latest_shard(dir: &str) -> Result<Option<EcoShard>> {
-    let mut entries: Vec<_> = fs::read_dir(dir)?
-        .filter_map(|e| e.ok())
-        .collect();
-    entries.sort_by_key(|e| e.file_name());
-    if let Some(last) = entries.last() {
-        let mut path = PathBuf::from(dir);
-        path.push(last.file_name());
-        let raw = fs::read_to_string(path)?;
-        let shard: EcoShard = serde_json::from_str(&raw)?;
-        Ok(Some(shard))
-    } else {
-        Ok(None)
+/// Readings for `node_id` from shards generated in `[from, to)`, oldest
+/// first. Open bounds cover the whole store.
+pub fn node_history(
+    dir: &Path,
+    node_id: &str,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> Result<Vec<(DateTime<Utc>, CeimNodeState)>> {
+    let store = open_store(dir)?;
+    let entries = store.in_range(
+        from.unwrap_or(DateTime::<Utc>::MIN_UTC),
+        to.unwrap_or(DateTime::<Utc>::MAX_UTC),
+    );
+    let mut out = Vec::new();
+    for entry in entries {
+        let shard = read_shard(&store, entry)?;
+        out.extend(
+            shard
+                .nodes
+                .into_iter()
+                .filter(|n| n.node_id == node_id)
+                .map(|n| (shard.generated_at, n)),
+        );
     }
+    Ok(out)
 }
 
-pub fn band_for_score(k_n_norm: f64) -> f64 {
-    if k_n_norm < 0.3 {
-        0.1
-    } else if k_n_norm < 0.7 {
-        0.5
-    } else {
-        0.9
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ceim_shard_schema::write_shard;
+    use chrono::Duration;
+
+    fn node(node_id: &str, k_n: f64, at: DateTime<Utc>) -> CeimNodeState {
+        CeimNodeState {
+            node_id: node_id.to_string(),
+            contaminant: "nitrate".to_string(),
+            k_n,
+            mass_load_g: Some(1.0),
+            binding_authority: Some("epa".to_string()),
+            unregulated: false,
+            ecoimpact_score: k_n,
+            window: None,
+            last_updated: at,
+        }
+    }
+
+    #[test]
+    fn history_filters_node_and_range() {
+        let dir = std::env::temp_dir().join(format!("econet-history-{}", std::process::id()));
+        assert!(node_history(&dir, "n1", None, None).unwrap().is_empty());
+        assert!(!dir.exists());
+        let mut store = ShardStore::open(&dir, SHARD_PREFIX).unwrap();
+        let at = |h| DateTime::<Utc>::UNIX_EPOCH + Duration::hours(h);
+        for h in 0..4 {
+            let nodes = vec![node("n1", h as f64, at(h)), node("n2", 9.0, at(h))];
+            write_shard(&mut store, &CeimShard::new(at(h), nodes)).unwrap();
+        }
+
+        let all = node_history(&dir, "n1", None, None).unwrap();
+        assert_eq!(all.len(), 4);
+        let some = node_history(&dir, "n1", Some(at(1)), Some(at(3))).unwrap();
+        let k_n: Vec<f64> = some.iter().map(|(_, n)| n.k_n).collect();
+        assert_eq!(k_n, vec![1.0, 2.0]);
+        assert_eq!(some[0].0, at(1));
+        assert!(node_history(&dir, "n3", None, None).unwrap().is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 }
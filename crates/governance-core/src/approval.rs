@@ -0,0 +1,325 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{ReversalConditions, Role, RoleSet};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ApprovalError {
+    #[error("signer {0} is not registered")]
+    UnknownSigner(String),
+    #[error("signer {did} is registered as {registered:?}, not {claimed:?}")]
+    RoleMismatch {
+        did: String,
+        registered: Role,
+        claimed: Role,
+    },
+    #[error("signature from {0} is bound to a different request")]
+    WrongRequest(String),
+    #[error("signature from {0} has expired")]
+    Expired(String),
+    #[error("signature from {0} does not verify")]
+    BadSignature(String),
+    #[error("signer {0} signed more than once")]
+    DuplicateSigner(String),
+    #[error("request {0} has already been granted")]
+    Replayed(Uuid),
+    #[error("signer {0} is already registered")]
+    AlreadyRegistered(String),
+    #[error("invalid public key for {0}")]
+    InvalidKey(String),
+}
+
+/// A reversal or emergency request that approvers sign.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ReversalRequest {
+    pub request_id: Uuid,
+    pub conditions: ReversalConditions,
+    pub issued_at: DateTime<Utc>,
+}
+
+impl ReversalRequest {
+    pub fn new(conditions: ReversalConditions, issued_at: DateTime<Utc>) -> Self {
+        Self {
+            request_id: Uuid::new_v4(),
+            conditions,
+            issued_at,
+        }
+    }
+
+    /// SHA-256 over the JSON encoding of the conditions.
+    pub fn conditions_digest(&self) -> String {
+        let json = serde_json::to_vec(&self.conditions).expect("conditions serialize");
+        hex::encode(Sha256::digest(json))
+    }
+}
+
+/// The exact bytes a signer commits to: one request, one role, one expiry.
+#[derive(Serialize)]
+struct SignedPayload<'a> {
+    domain: &'static str,
+    request_id: Uuid,
+    conditions_sha256: String,
+    did: &'a str,
+    role: &'a Role,
+    expires_at: DateTime<Utc>,
+}
+
+const SIGNATURE_DOMAIN: &str = "governance-core/reversal-approval/v1";
+
+/// One approver's signature over a `ReversalRequest`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RoleSignature {
+    pub did: String,
+    pub role: Role,
+    pub request_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+    /// Hex-encoded ed25519 signature.
+    pub signature: String,
+}
+
+impl RoleSignature {
+    fn payload(
+        request: &ReversalRequest,
+        did: &str,
+        role: &Role,
+        expires_at: DateTime<Utc>,
+    ) -> Vec<u8> {
+        serde_json::to_vec(&SignedPayload {
+            domain: SIGNATURE_DOMAIN,
+            request_id: request.request_id,
+            conditions_sha256: request.conditions_digest(),
+            did,
+            role,
+            expires_at,
+        })
+        .expect("payload serializes")
+    }
+
+    pub fn sign(
+        key: &SigningKey,
+        did: &str,
+        role: Role,
+        request: &ReversalRequest,
+        expires_at: DateTime<Utc>,
+    ) -> Self {
+        let signature = key.sign(&Self::payload(request, did, &role, expires_at));
+        Self {
+            did: did.to_string(),
+            role,
+            request_id: request.request_id,
+            expires_at,
+            signature: hex::encode(signature.to_bytes()),
+        }
+    }
+}
+
+/// Trusted approvers: each DID holds exactly one role and one key.
+#[derive(Clone, Debug, Default)]
+pub struct SignerRegistry {
+    signers: HashMap<String, (Role, VerifyingKey)>,
+}
+
+impl SignerRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(
+        &mut self,
+        did: &str,
+        role: Role,
+        public_key: &[u8; 32],
+    ) -> Result<(), ApprovalError> {
+        if self.signers.contains_key(did) {
+            return Err(ApprovalError::AlreadyRegistered(did.to_string()));
+        }
+        let key = VerifyingKey::from_bytes(public_key)
+            .map_err(|_| ApprovalError::InvalidKey(did.to_string()))?;
+        self.signers.insert(did.to_string(), (role, key));
+        Ok(())
+    }
+
+    /// Check every signature and build the role set from distinct signers.
+    /// Any invalid, expired, misbound or duplicated signature rejects the set.
+    pub fn verify(
+        &self,
+        request: &ReversalRequest,
+        signatures: &[RoleSignature],
+        regulator_quorum_threshold: usize,
+        now: DateTime<Utc>,
+    ) -> Result<RoleSet, ApprovalError> {
+        let mut signers: BTreeMap<Role, BTreeSet<String>> = BTreeMap::new();
+        let mut seen = BTreeSet::new();
+        for sig in signatures {
+            let (role, key) = self
+                .signers
+                .get(&sig.did)
+                .ok_or_else(|| ApprovalError::UnknownSigner(sig.did.clone()))?;
+            if *role != sig.role {
+                return Err(ApprovalError::RoleMismatch {
+                    did: sig.did.clone(),
+                    registered: role.clone(),
+                    claimed: sig.role.clone(),
+                });
+            }
+            if sig.request_id != request.request_id {
+                return Err(ApprovalError::WrongRequest(sig.did.clone()));
+            }
+            if sig.expires_at <= now {
+                return Err(ApprovalError::Expired(sig.did.clone()));
+            }
+            let bytes: [u8; 64] = hex::decode(&sig.signature)
+                .ok()
+                .and_then(|b| b.try_into().ok())
+                .ok_or_else(|| ApprovalError::BadSignature(sig.did.clone()))?;
+            let payload = RoleSignature::payload(request, &sig.did, &sig.role, sig.expires_at);
+            key.verify(&payload, &Signature::from_bytes(&bytes))
+                .map_err(|_| ApprovalError::BadSignature(sig.did.clone()))?;
+            if !seen.insert(sig.did.clone()) {
+                return Err(ApprovalError::DuplicateSigner(sig.did.clone()));
+            }
+            signers
+                .entry(role.clone())
+                .or_default()
+                .insert(sig.did.clone());
+        }
+        Ok(RoleSet::from_verified(signers, regulator_quorum_threshold))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DecisionReason, ReversalGate};
+    use chrono::Duration;
+
+    fn key(n: u8) -> SigningKey {
+        SigningKey::from_bytes(&[n; 32])
+    }
+
+    fn conditions() -> ReversalConditions {
+        ReversalConditions {
+            roh: 0.5,
+            decay: 0.9,
+            life_harm_flag: false,
+            explicit_reversal_order: true,
+            mitigations_exhausted: true,
+        }
+    }
+
+    fn setup() -> (ReversalGate, Vec<(String, Role, SigningKey)>) {
+        let approvers = vec![
+            ("did:host".to_string(), Role::Host, key(1)),
+            ("did:owner".to_string(), Role::OrganicCpuOwner, key(2)),
+            ("did:kernel".to_string(), Role::SovereignKernel, key(3)),
+            ("did:reg-a".to_string(), Role::Regulator, key(4)),
+            ("did:reg-b".to_string(), Role::Regulator, key(5)),
+        ];
+        let mut registry = SignerRegistry::new();
+        for (did, role, k) in &approvers {
+            registry
+                .register(did, role.clone(), k.verifying_key().as_bytes())
+                .unwrap();
+        }
+        (ReversalGate::new(registry, 2), approvers)
+    }
+
+    fn sign_all(
+        approvers: &[(String, Role, SigningKey)],
+        request: &ReversalRequest,
+        expires_at: DateTime<Utc>,
+    ) -> Vec<RoleSignature> {
+        approvers
+            .iter()
+            .map(|(did, role, k)| RoleSignature::sign(k, did, role.clone(), request, expires_at))
+            .collect()
+    }
+
+    #[test]
+    fn grants_with_distinct_valid_signers_once() {
+        let (mut gate, approvers) = setup();
+        let now = Utc::now();
+        let request = ReversalRequest::new(conditions(), now);
+        let sigs = sign_all(&approvers, &request, now + Duration::minutes(10));
+        assert_eq!(
+            gate.evaluate(&request, &sigs, now),
+            Ok(DecisionReason::GrantedEmergency)
+        );
+        assert_eq!(
+            gate.evaluate(&request, &sigs, now),
+            Err(ApprovalError::Replayed(request.request_id))
+        );
+    }
+
+    #[test]
+    fn repeated_regulator_does_not_count_twice() {
+        let (mut gate, approvers) = setup();
+        let now = Utc::now();
+        let request = ReversalRequest::new(conditions(), now);
+        let mut sigs = sign_all(&approvers[..4], &request, now + Duration::minutes(10));
+        sigs.push(sigs[3].clone());
+        assert_eq!(
+            gate.evaluate(&request, &sigs, now),
+            Err(ApprovalError::DuplicateSigner("did:reg-a".to_string()))
+        );
+        sigs.pop();
+        assert_eq!(
+            gate.evaluate(&request, &sigs, now),
+            Ok(DecisionReason::DeniedNeuromorphGodUnsatisfied)
+        );
+    }
+
+    #[test]
+    fn rejects_expired_rebound_and_tampered_signatures() {
+        let (mut gate, approvers) = setup();
+        let now = Utc::now();
+        let request = ReversalRequest::new(conditions(), now);
+
+        let expired = sign_all(&approvers, &request, now - Duration::seconds(1));
+        assert!(matches!(
+            gate.evaluate(&request, &expired, now),
+            Err(ApprovalError::Expired(_))
+        ));
+
+        let other = ReversalRequest::new(conditions(), now);
+        let replayed = sign_all(&approvers, &other, now + Duration::minutes(10));
+        assert!(matches!(
+            gate.evaluate(&request, &replayed, now),
+            Err(ApprovalError::WrongRequest(_))
+        ));
+
+        let mut tampered = request.clone();
+        tampered.conditions.life_harm_flag = true;
+        let sigs = sign_all(&approvers, &request, now + Duration::minutes(10));
+        assert!(matches!(
+            gate.evaluate(&tampered, &sigs, now),
+            Err(ApprovalError::BadSignature(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_claimed_role_not_registered() {
+        let (mut gate, approvers) = setup();
+        let now = Utc::now();
+        let request = ReversalRequest::new(conditions(), now);
+        let mut sigs = sign_all(&approvers, &request, now + Duration::minutes(10));
+        let (did, _, k) = &approvers[0];
+        sigs[0] = RoleSignature::sign(
+            k,
+            did,
+            Role::Regulator,
+            &request,
+            now + Duration::minutes(10),
+        );
+        assert!(matches!(
+            gate.evaluate(&request, &sigs, now),
+            Err(ApprovalError::RoleMismatch { .. })
+        ));
+    }
+}
@@ -1,20 +1,63 @@
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
 use uuid::Uuid;
 
+use crate::{DecisionReason, ReversalGate};
+
+/// `prev_hash` of the first record in a chain.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+#[derive(Debug, Error)]
+pub enum LedgerError {
+    #[error("ledger I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("ledger record {line} is malformed: {source}")]
+    Malformed {
+        line: usize,
+        source: serde_json::Error,
+    },
+    #[error("hash chain broken at record {0}")]
+    ChainBroken(usize),
+    #[error("event {0} is already in the ledger")]
+    DuplicateEvent(Uuid),
+    #[error("event {0} grants a reversal without a GrantedEmergency decision")]
+    GrantWithoutDecision(Uuid),
+    #[error(
+        "event {event} grants a reversal for request {request}, which the gate has not granted"
+    )]
+    GrantNotConsumed { event: Uuid, request: Uuid },
+    #[error("reversal request {0} is already granted by another event")]
+    GrantReused(Uuid),
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct EthicsFlags {
     pub ethics_ok: bool,
     pub life_harm_flag: LifeHarmFlag,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+/// Ordered by severity: `None < Potential < Confirmed`.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum LifeHarmFlag {
     None,
     Potential,
     Confirmed,
 }
 
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReversalStatus {
+    NotProposed,
+    Denied,
+    Granted,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct DeedEvent {
     pub id: Uuid,
@@ -25,27 +68,419 @@ pub struct DeedEvent {
     pub mp_delta: f64,
     pub reversal_proposed: bool,
     pub reversal_granted: bool,
+    /// Gate decision behind a reversal; required when `reversal_granted`.
+    #[serde(default)]
+    pub decision: Option<DecisionReason>,
+    /// `ReversalRequest` the gate consumed when granting this reversal;
+    /// required when `reversal_granted`.
+    #[serde(default)]
+    pub reversal_request_id: Option<Uuid>,
+    /// CHURCH compensation paid to the actor by this event.
+    #[serde(default)]
+    pub church_paid: f64,
+}
+
+impl DeedEvent {
+    pub fn reversal_status(&self) -> ReversalStatus {
+        match (self.reversal_proposed, self.reversal_granted) {
+            (_, true) => ReversalStatus::Granted,
+            (true, false) => ReversalStatus::Denied,
+            (false, false) => ReversalStatus::NotProposed,
+        }
+    }
+}
+
+/// One persisted line: the event plus its link in the hash chain.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LedgerRecord {
+    pub prev_hash: String,
+    pub hash: String,
+    pub event: DeedEvent,
 }
 
+impl LedgerRecord {
+    fn seal(prev_hash: &str, event: DeedEvent) -> Self {
+        let hash = record_hash(prev_hash, &event);
+        Self {
+            prev_hash: prev_hash.to_string(),
+            hash,
+            event,
+        }
+    }
+}
+
+fn record_hash(prev_hash: &str, event: &DeedEvent) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(prev_hash.as_bytes());
+    hasher.update(serde_json::to_vec(event).expect("deed event serializes"));
+    hex::encode(hasher.finalize())
+}
+
+/// Append-only deed ledger. Each record hashes its predecessor; when opened
+/// from a file every append is written through as one JSON line.
 #[derive(Default)]
 pub struct DeedLedger {
-    events: Vec<DeedEvent>,
+    records: Vec<LedgerRecord>,
+    path: Option<PathBuf>,
 }
 
 impl DeedLedger {
     pub fn new() -> Self {
-        Self { events: Vec::new() }
+        Self {
+            records: Vec::new(),
+            path: None,
+        }
+    }
+
+    /// Load and verify an NDJSON ledger, creating it if absent.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, LedgerError> {
+        let path = path.as_ref().to_path_buf();
+        let mut records = Vec::new();
+        if path.exists() {
+            for (i, line) in BufReader::new(File::open(&path)?).lines().enumerate() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let record =
+                    serde_json::from_str(&line).map_err(|source| LedgerError::Malformed {
+                        line: i + 1,
+                        source,
+                    })?;
+                records.push(record);
+            }
+        }
+        let ledger = Self {
+            records,
+            path: Some(path),
+        };
+        ledger.verify_chain()?;
+        Ok(ledger)
+    }
+
+    /// Append an event that does not grant a reversal; grants go through
+    /// [`DeedLedger::append_grant`].
+    pub fn append(&mut self, event: DeedEvent) -> Result<&LedgerRecord, LedgerError> {
+        self.push(event, None)
+    }
+
+    /// Append a granted reversal, checking that `gate` consumed the request
+    /// the event names.
+    pub fn append_grant(
+        &mut self,
+        event: DeedEvent,
+        gate: &ReversalGate,
+    ) -> Result<&LedgerRecord, LedgerError> {
+        self.push(event, Some(gate))
+    }
+
+    fn push(
+        &mut self,
+        event: DeedEvent,
+        gate: Option<&ReversalGate>,
+    ) -> Result<&LedgerRecord, LedgerError> {
+        if event.reversal_granted {
+            let request = self.check_grant(&event, self.records.len())?;
+            if !gate.is_some_and(|g| g.is_consumed(&request)) {
+                return Err(LedgerError::GrantNotConsumed {
+                    event: event.id,
+                    request,
+                });
+            }
+        }
+        if self.records.iter().any(|r| r.event.id == event.id) {
+            return Err(LedgerError::DuplicateEvent(event.id));
+        }
+        let record = LedgerRecord::seal(self.head_hash(), event);
+        if let Some(path) = &self.path {
+            let mut line = serde_json::to_string(&record).expect("ledger record serializes");
+            line.push('\n');
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            file.write_all(line.as_bytes())?;
+            file.sync_data()?;
+        }
+        self.records.push(record);
+        Ok(self.records.last().expect("just pushed"))
+    }
+
+    /// Recompute every link; also re-checks that each grant names a
+    /// GrantedEmergency decision and a request no earlier grant used.
+    pub fn verify_chain(&self) -> Result<(), LedgerError> {
+        let mut prev = GENESIS_HASH;
+        for (i, record) in self.records.iter().enumerate() {
+            if record.prev_hash != prev || record.hash != record_hash(prev, &record.event) {
+                return Err(LedgerError::ChainBroken(i));
+            }
+            if record.event.reversal_granted {
+                self.check_grant(&record.event, i)?;
+            }
+            prev = &record.hash;
+        }
+        Ok(())
+    }
+
+    /// Ledger-side grant rules against the first `upto` records; returns the
+    /// request the grant is bound to.
+    fn check_grant(&self, event: &DeedEvent, upto: usize) -> Result<Uuid, LedgerError> {
+        let request = match (&event.decision, event.reversal_request_id) {
+            (Some(DecisionReason::GrantedEmergency), Some(request)) => request,
+            _ => return Err(LedgerError::GrantWithoutDecision(event.id)),
+        };
+        if self.records[..upto]
+            .iter()
+            .any(|r| r.event.reversal_granted && r.event.reversal_request_id == Some(request))
+        {
+            return Err(LedgerError::GrantReused(request));
+        }
+        Ok(request)
+    }
+
+    /// Requests consumed by the reversals this ledger records as granted.
+    pub fn granted_requests(&self) -> impl Iterator<Item = Uuid> + '_ {
+        self.all()
+            .filter(|e| e.reversal_granted)
+            .filter_map(|e| e.reversal_request_id)
+    }
+
+    pub fn head_hash(&self) -> &str {
+        self.records.last().map_or(GENESIS_HASH, |r| &r.hash)
     }
 
-    pub fn append(&mut self, event: DeedEvent) {
-        self.events.push(event);
+    pub fn records(&self) -> &[LedgerRecord] {
+        &self.records
     }
 
-    pub fn all(&self) -> &[DeedEvent] {
-        &self.events
+    pub fn all(&self) -> impl Iterator<Item = &DeedEvent> {
+        self.records.iter().map(|r| &r.event)
+    }
+
+    pub fn by_actor<'a>(&'a self, actor: &'a str) -> impl Iterator<Item = &'a DeedEvent> {
+        self.all().filter(move |e| e.actor == actor)
+    }
+
+    /// Events with `from <= timestamp < to`.
+    pub fn in_range(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> impl Iterator<Item = &DeedEvent> {
+        self.all()
+            .filter(move |e| e.timestamp >= from && e.timestamp < to)
+    }
+
+    /// Events whose life-harm flag is at least `min`.
+    pub fn with_life_harm_at_least(&self, min: LifeHarmFlag) -> impl Iterator<Item = &DeedEvent> {
+        self.all()
+            .filter(move |e| e.ethics_flags.life_harm_flag >= min)
+    }
+
+    pub fn with_reversal_status(&self, status: ReversalStatus) -> impl Iterator<Item = &DeedEvent> {
+        self.all().filter(move |e| e.reversal_status() == status)
     }
 
     pub fn total_mp(&self) -> f64 {
-        self.events.iter().map(|e| e.mp_delta).sum()
+        self.all().map(|e| e.mp_delta).sum()
+    }
+
+    pub fn mp_balance(&self, actor: &str) -> f64 {
+        self.by_actor(actor).map(|e| e.mp_delta).sum()
+    }
+
+    pub fn mp_balances(&self) -> BTreeMap<String, f64> {
+        let mut balances = BTreeMap::new();
+        for e in self.all() {
+            *balances.entry(e.actor.clone()).or_insert(0.0) += e.mp_delta;
+        }
+        balances
+    }
+
+    /// MP debt an actor has accrued through denied reversals.
+    pub fn denied_reversal_debt(&self, actor: &str) -> f64 {
+        let debt: f64 = self
+            .by_actor(actor)
+            .filter(|e| e.reversal_status() == ReversalStatus::Denied)
+            .map(|e| -e.mp_delta)
+            .sum();
+        debt.max(0.0)
+    }
+
+    /// CHURCH compensation already paid to an actor.
+    pub fn church_paid(&self, actor: &str) -> f64 {
+        self.by_actor(actor).map(|e| e.church_paid).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn event(actor: &str, minute: i64, mp: f64, harm: LifeHarmFlag) -> DeedEvent {
+        DeedEvent {
+            id: Uuid::new_v4(),
+            timestamp: Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()
+                + Duration::minutes(minute),
+            actor: actor.to_string(),
+            description: "deed".to_string(),
+            ethics_flags: EthicsFlags {
+                ethics_ok: harm == LifeHarmFlag::None,
+                life_harm_flag: harm,
+            },
+            mp_delta: mp,
+            reversal_proposed: false,
+            reversal_granted: false,
+            decision: None,
+            reversal_request_id: None,
+            church_paid: 0.0,
+        }
+    }
+
+    #[test]
+    fn persists_and_detects_tampering() {
+        let path = std::env::temp_dir().join(format!("deeds-{}.ndjson", Uuid::new_v4()));
+        let mut ledger = DeedLedger::open(&path).unwrap();
+        ledger
+            .append(event("alice", 0, 0.4, LifeHarmFlag::None))
+            .unwrap();
+        ledger
+            .append(event("bob", 1, -0.2, LifeHarmFlag::Potential))
+            .unwrap();
+        let head = ledger.head_hash().to_string();
+
+        let reopened = DeedLedger::open(&path).unwrap();
+        assert_eq!(reopened.head_hash(), head);
+        assert_eq!(reopened.records().len(), 2);
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, text.replace("\"bob\"", "\"eve\"")).unwrap();
+        assert!(matches!(
+            DeedLedger::open(&path),
+            Err(LedgerError::ChainBroken(1))
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn granted_reversal_requires_consumed_request() {
+        use crate::{
+            ApprovalError, ReversalConditions, ReversalRequest, Role, RoleSignature, SignerRegistry,
+        };
+        use ed25519_dalek::SigningKey;
+
+        let now = Utc::now();
+        let mut ledger = DeedLedger::new();
+        let mut gate = ReversalGate::new(SignerRegistry::new(), 1);
+        let request = ReversalRequest::new(
+            ReversalConditions {
+                roh: 0.5,
+                decay: 0.9,
+                life_harm_flag: false,
+                explicit_reversal_order: true,
+                mitigations_exhausted: true,
+            },
+            now,
+        );
+        let mut granted = event("alice", 0, 0.0, LifeHarmFlag::None);
+        granted.reversal_proposed = true;
+        granted.reversal_granted = true;
+        granted.decision = Some(DecisionReason::GrantedEmergency);
+        assert!(matches!(
+            ledger.append_grant(granted.clone(), &gate),
+            Err(LedgerError::GrantWithoutDecision(_))
+        ));
+
+        granted.reversal_request_id = Some(request.request_id);
+        assert!(matches!(
+            ledger.append(granted.clone()),
+            Err(LedgerError::GrantNotConsumed { .. })
+        ));
+        assert!(matches!(
+            ledger.append_grant(granted.clone(), &gate),
+            Err(LedgerError::GrantNotConsumed { .. })
+        ));
+
+        let approvers = [
+            ("did:host", Role::Host),
+            ("did:owner", Role::OrganicCpuOwner),
+            ("did:kernel", Role::SovereignKernel),
+            ("did:reg", Role::Regulator),
+        ];
+        let mut registry = SignerRegistry::new();
+        let mut sigs = Vec::new();
+        for (n, (did, role)) in approvers.into_iter().enumerate() {
+            let key = SigningKey::from_bytes(&[n as u8 + 1; 32]);
+            registry
+                .register(did, role.clone(), key.verifying_key().as_bytes())
+                .unwrap();
+            sigs.push(RoleSignature::sign(
+                &key,
+                did,
+                role,
+                &request,
+                now + Duration::minutes(10),
+            ));
+        }
+        gate = ReversalGate::new(registry, 1);
+        assert_eq!(
+            gate.evaluate(&request, &sigs, now),
+            Ok(DecisionReason::GrantedEmergency)
+        );
+        ledger.append_grant(granted.clone(), &gate).unwrap();
+
+        // A gate restarted from the ledger still refuses the replay.
+        let mut restarted = ReversalGate::new(gate.registry().clone(), 1);
+        restarted.restore_consumed(&ledger);
+        assert_eq!(
+            restarted.evaluate(&request, &sigs, now),
+            Err(ApprovalError::Replayed(request.request_id))
+        );
+
+        granted.id = Uuid::new_v4();
+        assert!(matches!(
+            ledger.append_grant(granted, &gate),
+            Err(LedgerError::GrantReused(_))
+        ));
+        assert_eq!(
+            ledger.with_reversal_status(ReversalStatus::Granted).count(),
+            1
+        );
+    }
+
+    #[test]
+    fn queries_and_balances() {
+        let mut ledger = DeedLedger::new();
+        ledger
+            .append(event("alice", 0, 0.5, LifeHarmFlag::None))
+            .unwrap();
+        ledger
+            .append(event("alice", 10, -0.2, LifeHarmFlag::Confirmed))
+            .unwrap();
+        let mut denied = event("bob", 20, -0.7, LifeHarmFlag::Potential);
+        denied.reversal_proposed = true;
+        denied.decision = Some(DecisionReason::DeniedOwnerNotSigned);
+        ledger.append(denied).unwrap();
+
+        assert_eq!(ledger.by_actor("alice").count(), 2);
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 5, 0).unwrap();
+        assert_eq!(
+            ledger.in_range(start, start + Duration::hours(1)).count(),
+            2
+        );
+        assert_eq!(
+            ledger
+                .with_life_harm_at_least(LifeHarmFlag::Potential)
+                .count(),
+            2
+        );
+        assert!((ledger.mp_balance("alice") - 0.3).abs() < 1e-9);
+        assert_eq!(ledger.mp_balances().len(), 2);
+        assert!((ledger.denied_reversal_debt("bob") - 0.7).abs() < 1e-9);
+        assert_eq!(ledger.denied_reversal_debt("alice"), 0.0);
+
+        assert!((crate::church_compensation_for_denial(&ledger, "bob") - 0.7).abs() < 1e-9);
+        let mut payout = event("bob", 30, 0.0, LifeHarmFlag::None);
+        payout.church_paid = 0.5;
+        ledger.append(payout).unwrap();
+        assert!((crate::church_compensation_for_denial(&ledger, "bob") - 0.2).abs() < 1e-9);
     }
 }
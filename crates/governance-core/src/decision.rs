@@ -1,6 +1,12 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use reversal_policy::{ReversalInputs, ReversalPolicy, ReversalVerdict, SaferAlternativeEvidence};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
-use crate::RoleSet;
+use crate::approval::{ApprovalError, ReversalRequest, RoleSignature, SignerRegistry};
+use crate::{DeedLedger, RoleSet};
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct ReversalConditions {
@@ -12,8 +18,13 @@ pub struct ReversalConditions {
 }
 
 impl ReversalConditions {
+    /// Derived per `aln/reversal_policy.aln`.
     pub fn nosaferalternative(&self) -> bool {
-        self.mitigations_exhausted && self.roh > 0.3 && self.decay > 0.8
+        ReversalPolicy::standard().no_safer_alternative(&SaferAlternativeEvidence {
+            mitigations_exhausted: self.mitigations_exhausted,
+            roh: self.roh,
+            decay: self.decay,
+        })
     }
 }
 
@@ -26,10 +37,93 @@ pub enum DecisionReason {
     DeniedLifeHarmFlag,
 }
 
-pub struct ReversalGate;
+/// Gate for reversal and emergency requests. Roles come only from verified
+/// signatures, and a granted request id is consumed so it cannot be replayed.
+#[derive(Clone, Debug)]
+pub struct ReversalGate {
+    registry: SignerRegistry,
+    regulator_quorum_threshold: usize,
+    consumed: HashSet<Uuid>,
+}
 
 impl ReversalGate {
-    pub fn evaluate(role_set: &RoleSet, cond: &ReversalConditions) -> DecisionReason {
+    pub fn new(registry: SignerRegistry, regulator_quorum_threshold: usize) -> Self {
+        Self {
+            registry,
+            regulator_quorum_threshold,
+            consumed: HashSet::new(),
+        }
+    }
+
+    pub fn registry(&self) -> &SignerRegistry {
+        &self.registry
+    }
+
+    pub fn is_consumed(&self, request_id: &Uuid) -> bool {
+        self.consumed.contains(request_id)
+    }
+
+    /// Mark every request `ledger` records as granted consumed, so a
+    /// restarted gate cannot grant them again. Grants are only durable once
+    /// recorded with [`DeedLedger::append_grant`].
+    pub fn restore_consumed(&mut self, ledger: &DeedLedger) {
+        self.consumed.extend(ledger.granted_requests());
+    }
+
+    pub fn evaluate(
+        &mut self,
+        request: &ReversalRequest,
+        signatures: &[RoleSignature],
+        now: DateTime<Utc>,
+    ) -> Result<DecisionReason, ApprovalError> {
+        if self.consumed.contains(&request.request_id) {
+            return Err(ApprovalError::Replayed(request.request_id));
+        }
+        let role_set =
+            self.registry
+                .verify(request, signatures, self.regulator_quorum_threshold, now)?;
+        let reason = Self::decide(&role_set, &request.conditions);
+        if reason == DecisionReason::GrantedEmergency {
+            self.consumed.insert(request.request_id);
+        }
+        Ok(reason)
+    }
+
+    /// Decision rules over an already verified role set, delegated to the
+    /// shared reversal policy. The explicit order only counts when the owner
+    /// signed it.
+    pub fn decide(role_set: &RoleSet, cond: &ReversalConditions) -> DecisionReason {
+        let inputs = ReversalInputs {
+            life_harm_flag: cond.life_harm_flag,
+            neuromorph_god_satisfied: role_set.neuromorph_god_satisfied(),
+            explicit_reversal_order: role_set.has_owner_signature() && cond.explicit_reversal_order,
+            no_safer_alternative: cond.nosaferalternative(),
+        };
+        match ReversalPolicy::standard().evaluate(&inputs) {
+            ReversalVerdict::Granted => DecisionReason::GrantedEmergency,
+            ReversalVerdict::DeniedLifeHarmFlag => DecisionReason::DeniedLifeHarmFlag,
+            ReversalVerdict::DeniedNeuromorphGodUnsatisfied => {
+                DecisionReason::DeniedNeuromorphGodUnsatisfied
+            }
+            ReversalVerdict::DeniedExplicitOrderMissing => DecisionReason::DeniedOwnerNotSigned,
+            ReversalVerdict::DeniedNoSaferAlternativeNotProved => {
+                DecisionReason::DeniedNoSaferAlternativeNotProved
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::Role;
+
+    /// The gate's rules before they moved into `reversal-policy`.
+    fn legacy_decide(role_set: &RoleSet, cond: &ReversalConditions) -> DecisionReason {
         if cond.life_harm_flag {
             return DecisionReason::DeniedLifeHarmFlag;
         }
@@ -39,9 +133,101 @@ impl ReversalGate {
         if !role_set.has_owner_signature() || !cond.explicit_reversal_order {
             return DecisionReason::DeniedOwnerNotSigned;
         }
-        if !cond.nosaferalternative() {
+        if !(cond.mitigations_exhausted && cond.roh > 0.3 && cond.decay > 0.8) {
             return DecisionReason::DeniedNoSaferAlternativeNotProved;
         }
         DecisionReason::GrantedEmergency
     }
+
+    fn role_sets() -> Vec<RoleSet> {
+        let all = [
+            Role::Host,
+            Role::OrganicCpuOwner,
+            Role::SovereignKernel,
+            Role::Regulator,
+        ];
+        (0u8..16)
+            .map(|mask| {
+                let mut signers = BTreeMap::new();
+                for (i, role) in all.iter().enumerate() {
+                    if mask & (1 << i) != 0 {
+                        signers.insert(role.clone(), BTreeSet::from([format!("did:{i}")]));
+                    }
+                }
+                RoleSet::from_verified(signers, 1)
+            })
+            .collect()
+    }
+
+    fn ratio() -> impl Strategy<Value = f64> {
+        prop_oneof![
+            -0.5..1.5f64,
+            prop::sample::select(vec![0.3, 0.8, f64::NAN, f64::INFINITY]),
+            any::<f64>(),
+        ]
+    }
+
+    proptest! {
+        /// Every consumer of `reversal-policy` buildable in this workspace
+        /// must agree with the shared policy and with the pre-extraction rules.
+        #[test]
+        fn consumers_agree_with_shared_policy(
+            mask in 0usize..16,
+            roh in ratio(),
+            decay in ratio(),
+            life_harm_flag: bool,
+            explicit_reversal_order: bool,
+            mitigations_exhausted: bool,
+        ) {
+            let policy = ReversalPolicy::standard();
+            let role_set = &role_sets()[mask];
+            let cond = ReversalConditions {
+                roh,
+                decay,
+                life_harm_flag,
+                explicit_reversal_order,
+                mitigations_exhausted,
+            };
+            let evidence = SaferAlternativeEvidence { mitigations_exhausted, roh, decay };
+            let no_safer_alternative = policy.no_safer_alternative(&evidence);
+
+            prop_assert_eq!(cond.nosaferalternative(), no_safer_alternative);
+            let snapshot = neuromorph_sim::EnvelopeSnapshot {
+                roh,
+                decay,
+                lifeforce: 0.5,
+                power: 0.5,
+                tech: 0.5,
+                nano: 0.5,
+                smart: 0.5,
+            };
+            prop_assert_eq!(
+                neuromorph_sim::nosaferalternative(&snapshot, mitigations_exhausted),
+                no_safer_alternative
+            );
+            let proof = neuromorph_sim::NoSaferAlternativeProof {
+                explored: 1,
+                roh_ceiling: neuromorph_sim::SAFE_ROH_CEILING,
+                best_roh: roh,
+                best_decay: decay,
+            };
+            prop_assert_eq!(
+                proof.establishes_nosaferalternative(),
+                policy.no_safer_alternative(&proof.evidence())
+            );
+
+            let decided = ReversalGate::decide(role_set, &cond);
+            prop_assert_eq!(&decided, &legacy_decide(role_set, &cond));
+            let granted = policy
+                .evaluate(&ReversalInputs {
+                    life_harm_flag,
+                    neuromorph_god_satisfied: role_set.neuromorph_god_satisfied(),
+                    explicit_reversal_order: role_set.has_owner_signature()
+                        && explicit_reversal_order,
+                    no_safer_alternative,
+                })
+                .is_granted();
+            prop_assert_eq!(decided == DecisionReason::GrantedEmergency, granted);
+        }
+    }
 }
@@ -1,16 +1,19 @@
-mod roles;
+mod approval;
 mod audit;
 mod decision;
+mod roles;
 
+pub use approval::{ApprovalError, ReversalRequest, RoleSignature, SignerRegistry};
+pub use audit::{
+    DeedEvent, DeedLedger, EthicsFlags, LedgerError, LedgerRecord, LifeHarmFlag, ReversalStatus,
+    GENESIS_HASH,
+};
+pub use decision::{DecisionReason, ReversalConditions, ReversalGate};
 pub use roles::{Role, RoleSet};
-pub use audit::{DeedEvent, EthicsFlags, LifeHarmFlag, DeedLedger};
-pub use decision::{ReversalConditions, DecisionReason, ReversalGate};
 
-/// Helper to compute CHURCH token compensation for denied downgrades.
-pub fn church_compensation_for_denial(mp_debt: f64) -> f64 {
-    if mp_debt <= 0.0 {
-        0.0
-    } else {
-        mp_debt.min(1.0)
-    }
+/// CHURCH token compensation still owed to `actor` for denied downgrades:
+/// the MP debt recorded against their denied reversals, capped at 1.0, less
+/// what the ledger shows already paid.
+pub fn church_compensation_for_denial(ledger: &DeedLedger, actor: &str) -> f64 {
+    (ledger.denied_reversal_debt(actor).min(1.0) - ledger.church_paid(actor)).max(0.0)
 }
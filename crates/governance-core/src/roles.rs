@@ -1,6 +1,8 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Role {
     Host,
     OrganicCpuOwner,
@@ -8,26 +10,54 @@ pub enum Role {
     SovereignKernel,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+/// Roles backed by verified signatures, keyed by the distinct DIDs that
+/// signed. Only `SignerRegistry::verify` can build one, so a role cannot be
+/// claimed without a signature and one DID never counts twice.
+#[derive(Serialize, Clone, Debug)]
 pub struct RoleSet {
-    pub roles: Vec<Role>,
-    pub regulator_quorum_threshold: usize,
+    signers: BTreeMap<Role, BTreeSet<String>>,
+    regulator_quorum_threshold: usize,
 }
 
 impl RoleSet {
+    pub(crate) fn from_verified(
+        signers: BTreeMap<Role, BTreeSet<String>>,
+        regulator_quorum_threshold: usize,
+    ) -> Self {
+        Self {
+            signers,
+            regulator_quorum_threshold,
+        }
+    }
+
+    pub fn has_role(&self, role: &Role) -> bool {
+        self.signers.get(role).is_some_and(|dids| !dids.is_empty())
+    }
+
+    /// Number of distinct DIDs that signed as `role`.
+    pub fn signer_count(&self, role: &Role) -> usize {
+        self.signers.get(role).map_or(0, BTreeSet::len)
+    }
+
+    pub fn signers(&self, role: &Role) -> impl Iterator<Item = &str> {
+        self.signers
+            .get(role)
+            .into_iter()
+            .flat_map(|dids| dids.iter().map(String::as_str))
+    }
+
+    pub fn regulator_quorum_threshold(&self) -> usize {
+        self.regulator_quorum_threshold
+    }
+
     pub fn neuromorph_god_satisfied(&self) -> bool {
-        self.roles.contains(&Role::Host)
-            && self.roles.contains(&Role::OrganicCpuOwner)
-            && self.roles.contains(&Role::SovereignKernel)
-            && self
-                .roles
-                .iter()
-                .filter(|&r| *r == Role::Regulator)
-                .count()
-                >= self.regulator_quorum_threshold
+        self.has_role(&Role::Host)
+            && self.has_role(&Role::OrganicCpuOwner)
+            && self.has_role(&Role::SovereignKernel)
+            && self.signer_count(&Role::Regulator) >= self.regulator_quorum_threshold
     }
 
     pub fn has_owner_signature(&self) -> bool {
-        self.roles.contains(&Role::OrganicCpuOwner)
+        self.has_role(&Role::OrganicCpuOwner)
     }
 }
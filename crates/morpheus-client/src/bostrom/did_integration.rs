@@ -86,6 +86,16 @@ impl DidKeyPair {
     }
 }
 
+impl crate::core::errority::RecordSigner for DidKeyPair {
+    fn signer_did(&self) -> &str {
+        &self.did.did
+    }
+
+    fn sign_hex(&self, message: &[u8]) -> Result<String, MorpheusError> {
+        Ok(hex::encode(self.sign(message)?.to_bytes()))
+    }
+}
+
 /// Verify a signature against a public key
 pub fn verify_signature(
     public_key_hex: &str,
@@ -0,0 +1,501 @@
+//! Errority engine: observed harm may only tighten limits
+//!
+//! Per `specs/interstitial-rights-v1.md`, an Errority event may only shrink
+//! polytopes, lower ceilings, or steepen hazard weights, never relax a limit.
+//! The engine turns an evidence-backed harm report into a [`TighteningDelta`],
+//! proves the delta is non-relaxing, applies it, and appends a signed
+//! [`TighteningRecord`] to the [`ErrorityJournal`].
+
+use crate::nanoswarm::microspace_guard::{
+    MicrospaceIntegrityGuard, DEFAULT_ACTIVITY_POWER_LIMIT_MW, DEFAULT_DENSITY_CEILING_PCT,
+    DEFAULT_OCCUPANCY_LIMIT_SECS,
+};
+use crate::types::{evidence::EvidenceBundle, guards::BciCeilingGuard, policy::PolicyProfile};
+use crate::MorpheusError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Which set of limits an Errority event applies to
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ErrorityTarget {
+    /// `PolicyProfile.biomech_policy.bci_ceiling` and the engine's `BciCeilingGuard`
+    BciCeiling,
+    /// Effect size, duty cycle and session length in `PolicyProfile.biomech_policy`
+    BiomechEnvelope,
+    /// A named corridor polytope in `PolicyProfile.corridor_polytopes`,
+    /// stored as half-space offsets `b` of `A·x ≤ b`
+    CorridorPolytope(String),
+    /// `MicrospaceIntegrityGuard` limits for one organism / ecosystem role
+    Microspace {
+        /// Occupant organism (density ceiling and occupancy limit key)
+        organism: String,
+        /// Ecosystem role (activity power limit key)
+        ecosystem_role: String,
+    },
+}
+
+/// An observed harm tied to the evidence that documents it
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HarmReport {
+    /// Unique report ID
+    pub report_id: String,
+    /// DID of the reporting party
+    pub reporter_did: String,
+    /// When the harm was observed
+    pub observed_at: DateTime<Utc>,
+    /// Human description of the harm
+    pub description: String,
+    /// Severity in (0.0, 1.0]
+    pub severity: f64,
+    /// Evidence bundle documenting the harm
+    pub evidence_bundle: EvidenceBundle,
+    /// Limits affected by the harm
+    pub target: ErrorityTarget,
+}
+
+/// One limit before and after tightening
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct LimitChange {
+    /// Dotted limit path, e.g. `biomech_policy.bci_ceiling`
+    pub limit: String,
+    /// Value before the Errority event
+    pub before: f64,
+    /// Value after the Errority event
+    pub after: f64,
+}
+
+/// The full set of limit changes computed for one harm report
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct TighteningDelta {
+    /// Individual limit changes (all upper bounds)
+    pub changes: Vec<LimitChange>,
+}
+
+impl TighteningDelta {
+    /// Prove that no change relaxes a limit: every value is finite and
+    /// `after <= before`. Returns the offending change otherwise.
+    pub fn prove_non_relaxing(&self) -> Result<(), MorpheusError> {
+        for change in &self.changes {
+            if !change.before.is_finite()
+                || !change.after.is_finite()
+                || change.after > change.before
+            {
+                return Err(MorpheusError::MonotonicityViolation(format!(
+                    "Errority delta relaxes {}: {} -> {}",
+                    change.limit, change.before, change.after
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Signs tightening records on behalf of a DID
+pub trait RecordSigner {
+    /// DID of the signer
+    fn signer_did(&self) -> &str;
+    /// Hex-encoded signature over `message`
+    fn sign_hex(&self, message: &[u8]) -> Result<String, MorpheusError>;
+}
+
+/// Signed journal entry for an applied tightening
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TighteningRecord {
+    /// Unique record ID (UUID)
+    pub record_id: String,
+    /// Harm report that caused the tightening
+    pub report_id: String,
+    /// Evidence bundle backing the harm report
+    pub evidence_bundle_id: String,
+    /// Policy profile the limits belong to
+    pub policy_profile: String,
+    /// Limits affected
+    pub target: ErrorityTarget,
+    /// Applied delta
+    pub delta: TighteningDelta,
+    /// When the tightening was applied
+    pub applied_at: DateTime<Utc>,
+    /// DID that signed the record
+    pub signer_did: String,
+    /// Hex signature over the record serialized with `signature: None`
+    pub signature: Option<String>,
+}
+
+impl TighteningRecord {
+    /// Bytes covered by the signature
+    pub fn signing_payload(&self) -> Result<Vec<u8>, MorpheusError> {
+        let unsigned = Self {
+            signature: None,
+            ..self.clone()
+        };
+        Ok(serde_json::to_vec(&unsigned)?)
+    }
+}
+
+/// Append-only journal of tightening records, optionally mirrored to an NDJSON file
+#[derive(Debug, Default)]
+pub struct ErrorityJournal {
+    /// NDJSON file each record is appended to, if any
+    pub path: Option<PathBuf>,
+    /// Records in application order
+    pub records: Vec<TighteningRecord>,
+}
+
+impl ErrorityJournal {
+    /// In-memory journal
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Journal mirrored to an NDJSON file
+    pub fn with_file(path: PathBuf) -> Self {
+        Self {
+            path: Some(path),
+            records: Vec::new(),
+        }
+    }
+
+    /// Append a signed record
+    pub fn append(&mut self, record: TighteningRecord) -> Result<(), MorpheusError> {
+        if record.signature.is_none() {
+            return Err(MorpheusError::AuditError(
+                "Errority records must be signed".to_string(),
+            ));
+        }
+        if let Some(path) = &self.path {
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|e| MorpheusError::AuditError(e.to_string()))?;
+            writeln!(file, "{}", serde_json::to_string(&record)?)
+                .map_err(|e| MorpheusError::AuditError(e.to_string()))?;
+        }
+        self.records.push(record);
+        Ok(())
+    }
+}
+
+/// Limits an Errority event may tighten
+pub struct ErrorityLimits<'a> {
+    /// Active policy profile
+    pub policy: &'a mut PolicyProfile,
+    /// BCI ceiling guard derived from the policy
+    pub bci_guard: &'a mut BciCeilingGuard,
+    /// Microspace guard, if the deployment has one
+    pub microspace: Option<&'a mut MicrospaceIntegrityGuard>,
+}
+
+/// Computes and applies Errority tightenings
+#[derive(Clone, Debug)]
+pub struct ErrorityEngine {
+    /// Largest fractional tightening a single event can cause (0.0–1.0)
+    pub max_step: f64,
+    /// Smallest fractional tightening applied for any accepted report
+    pub min_step: f64,
+}
+
+impl Default for ErrorityEngine {
+    fn default() -> Self {
+        Self {
+            max_step: 0.5,
+            min_step: 0.05,
+        }
+    }
+}
+
+impl ErrorityEngine {
+    /// Fractional tightening for a report: severity weighted by the
+    /// knowledge factor of its evidence, clamped to `[min_step, max_step]`
+    pub fn step_for(&self, report: &HarmReport) -> f64 {
+        (report.severity * report.evidence_bundle.knowledge_factor * self.max_step)
+            .clamp(self.min_step, self.max_step)
+    }
+
+    /// Compute the delta without applying it
+    pub fn compute_delta(
+        &self,
+        report: &HarmReport,
+        limits: &ErrorityLimits<'_>,
+    ) -> Result<TighteningDelta, MorpheusError> {
+        if !(report.severity > 0.0 && report.severity <= 1.0) {
+            return Err(MorpheusError::EvidenceInvalid(
+                "Harm severity must be in (0.0, 1.0]".to_string(),
+            ));
+        }
+        report
+            .evidence_bundle
+            .validate()
+            .map_err(MorpheusError::EvidenceInvalid)?;
+
+        let keep = 1.0 - self.step_for(report);
+        let shrink = |limit: &str, before: f64| LimitChange {
+            limit: limit.to_string(),
+            before,
+            after: before - before.abs() * (1.0 - keep),
+        };
+        let biomech = &limits.policy.biomech_policy;
+
+        let changes = match &report.target {
+            ErrorityTarget::BciCeiling => {
+                let guard_ceiling = shrink("bci_guard.ceiling", limits.bci_guard.ceiling);
+                let warn = LimitChange {
+                    limit: "bci_guard.warn_threshold".to_string(),
+                    before: limits.bci_guard.warn_threshold,
+                    after: (limits.bci_guard.warn_threshold * keep).min(guard_ceiling.after),
+                };
+                vec![
+                    shrink("biomech_policy.bci_ceiling", biomech.bci_ceiling),
+                    guard_ceiling,
+                    warn,
+                ]
+            }
+            ErrorityTarget::BiomechEnvelope => vec![
+                shrink("biomech_policy.max_effect_size", biomech.max_effect_size),
+                shrink("biomech_policy.max_duty_cycle", biomech.max_duty_cycle),
+                LimitChange {
+                    limit: "biomech_policy.max_session_minutes".to_string(),
+                    before: biomech.max_session_minutes as f64,
+                    after: (biomech.max_session_minutes as f64 * keep).floor(),
+                },
+            ],
+            ErrorityTarget::CorridorPolytope(name) => {
+                let offsets = limits.policy.corridor_polytopes.get(name).ok_or_else(|| {
+                    MorpheusError::PolicyError(format!("Unknown corridor polytope {name}"))
+                })?;
+                offsets
+                    .iter()
+                    .enumerate()
+                    .map(|(i, b)| shrink(&format!("corridor_polytopes.{name}[{i}]"), *b))
+                    .collect()
+            }
+            ErrorityTarget::Microspace {
+                organism,
+                ecosystem_role,
+            } => {
+                let guard = limits.microspace.as_deref().ok_or_else(|| {
+                    MorpheusError::GuardRejection("No microspace guard configured".to_string())
+                })?;
+                let density = guard
+                    .density_ceilings
+                    .get(organism)
+                    .copied()
+                    .unwrap_or(DEFAULT_DENSITY_CEILING_PCT);
+                let power = guard
+                    .activity_power_limits
+                    .get(ecosystem_role)
+                    .copied()
+                    .unwrap_or(DEFAULT_ACTIVITY_POWER_LIMIT_MW);
+                let occupancy = guard
+                    .occupancy_limits
+                    .get(organism)
+                    .copied()
+                    .unwrap_or(DEFAULT_OCCUPANCY_LIMIT_SECS);
+                vec![
+                    shrink(&format!("microspace.density_ceilings.{organism}"), density),
+                    shrink(
+                        &format!("microspace.activity_power_limits.{ecosystem_role}"),
+                        power,
+                    ),
+                    LimitChange {
+                        limit: format!("microspace.occupancy_limits.{organism}"),
+                        before: occupancy as f64,
+                        after: (occupancy as f64 * keep).floor(),
+                    },
+                ]
+            }
+        };
+
+        let delta = TighteningDelta { changes };
+        delta.prove_non_relaxing()?;
+        Ok(delta)
+    }
+
+    /// Compute, prove, journal and then apply the tightening for a harm
+    /// report; nothing is applied unless the record was journaled
+    pub fn ingest(
+        &self,
+        report: &HarmReport,
+        limits: ErrorityLimits<'_>,
+        signer: &dyn RecordSigner,
+        journal: &mut ErrorityJournal,
+    ) -> Result<TighteningRecord, MorpheusError> {
+        let delta = self.compute_delta(report, &limits)?;
+
+        let mut record = TighteningRecord {
+            record_id: uuid::Uuid::new_v4().to_string(),
+            report_id: report.report_id.clone(),
+            evidence_bundle_id: report.evidence_bundle.id.clone(),
+            policy_profile: limits.policy.name.clone(),
+            target: report.target.clone(),
+            delta: delta.clone(),
+            applied_at: Utc::now(),
+            signer_did: signer.signer_did().to_string(),
+            signature: None,
+        };
+        record.signature = Some(signer.sign_hex(&record.signing_payload()?)?);
+
+        journal.append(record.clone())?;
+        Self::apply(&report.target, &delta, limits);
+        info!(
+            "Errority {} tightened {} limits",
+            report.report_id,
+            delta.changes.len()
+        );
+        Ok(record)
+    }
+
+    fn apply(target: &ErrorityTarget, delta: &TighteningDelta, limits: ErrorityLimits<'_>) {
+        let after = |i: usize| delta.changes[i].after;
+        let biomech = &mut limits.policy.biomech_policy;
+        match target {
+            ErrorityTarget::BciCeiling => {
+                biomech.bci_ceiling = after(0);
+                limits.bci_guard.ceiling = after(1);
+                limits.bci_guard.warn_threshold = after(2);
+            }
+            ErrorityTarget::BiomechEnvelope => {
+                biomech.max_effect_size = after(0);
+                biomech.max_duty_cycle = after(1);
+                biomech.max_session_minutes = after(2) as u32;
+            }
+            ErrorityTarget::CorridorPolytope(name) => {
+                if let Some(offsets) = limits.policy.corridor_polytopes.get_mut(name) {
+                    for (i, b) in offsets.iter_mut().enumerate() {
+                        *b = after(i);
+                    }
+                }
+            }
+            ErrorityTarget::Microspace {
+                organism,
+                ecosystem_role,
+            } => {
+                if let Some(guard) = limits.microspace {
+                    guard.density_ceilings.insert(organism.clone(), after(0));
+                    guard
+                        .activity_power_limits
+                        .insert(ecosystem_role.clone(), after(1));
+                    guard
+                        .occupancy_limits
+                        .insert(organism.clone(), after(2) as u64);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::evidence::BiophysicalDomains;
+
+    struct TestSigner;
+
+    impl RecordSigner for TestSigner {
+        fn signer_did(&self) -> &str {
+            "did:bostrom:test"
+        }
+        fn sign_hex(&self, message: &[u8]) -> Result<String, MorpheusError> {
+            Ok(format!("{:x}", message.len()))
+        }
+    }
+
+    fn report(target: ErrorityTarget, severity: f64) -> HarmReport {
+        let mut evidence = EvidenceBundle::new("ev_harm".to_string(), 0.8, 0.1);
+        evidence.add_tag(BiophysicalDomains::thermal());
+        HarmReport {
+            report_id: "harm_001".to_string(),
+            reporter_did: "did:bostrom:reporter".to_string(),
+            observed_at: Utc::now(),
+            description: "cortical heating above baseline".to_string(),
+            severity,
+            evidence_bundle: evidence,
+            target,
+        }
+    }
+
+    #[test]
+    fn test_bci_ceiling_only_tightens() {
+        let mut policy = PolicyProfile::eu_neurorights();
+        let mut guard = BciCeilingGuard::new(0.20, 0.17);
+        let mut journal = ErrorityJournal::in_memory();
+        let record = ErrorityEngine::default()
+            .ingest(
+                &report(ErrorityTarget::BciCeiling, 0.5),
+                ErrorityLimits {
+                    policy: &mut policy,
+                    bci_guard: &mut guard,
+                    microspace: None,
+                },
+                &TestSigner,
+                &mut journal,
+            )
+            .unwrap();
+        assert!(policy.biomech_policy.bci_ceiling < 0.20);
+        assert!(guard.warn_threshold <= guard.ceiling);
+        assert!(record.signature.is_some());
+        assert_eq!(journal.records.len(), 1);
+    }
+
+    #[test]
+    fn test_unjournaled_tightening_is_not_applied() {
+        let mut policy = PolicyProfile::eu_neurorights();
+        let mut guard = BciCeilingGuard::new(0.20, 0.17);
+        let mut journal =
+            ErrorityJournal::with_file(PathBuf::from("/nonexistent/errority/journal.ndjson"));
+        let result = ErrorityEngine::default().ingest(
+            &report(ErrorityTarget::BciCeiling, 0.5),
+            ErrorityLimits {
+                policy: &mut policy,
+                bci_guard: &mut guard,
+                microspace: None,
+            },
+            &TestSigner,
+            &mut journal,
+        );
+        assert!(result.is_err());
+        assert_eq!(
+            policy.biomech_policy.bci_ceiling,
+            PolicyProfile::eu_neurorights().biomech_policy.bci_ceiling
+        );
+        assert_eq!(guard.ceiling, 0.20);
+        assert!(journal.records.is_empty());
+    }
+
+    #[test]
+    fn test_negative_polytope_offsets_shrink() {
+        let mut policy = PolicyProfile::new("t".to_string(), "1.0".to_string(), "t".to_string());
+        policy
+            .corridor_polytopes
+            .insert("phx".to_string(), vec![1.0, -0.5]);
+        let mut guard = BciCeilingGuard::new(0.25, 0.2);
+        let limits = ErrorityLimits {
+            policy: &mut policy,
+            bci_guard: &mut guard,
+            microspace: None,
+        };
+        let delta = ErrorityEngine::default()
+            .compute_delta(
+                &report(ErrorityTarget::CorridorPolytope("phx".to_string()), 1.0),
+                &limits,
+            )
+            .unwrap();
+        assert!(delta.changes[1].after < -0.5);
+    }
+
+    #[test]
+    fn test_relaxing_delta_is_rejected() {
+        let delta = TighteningDelta {
+            changes: vec![LimitChange {
+                limit: "biomech_policy.bci_ceiling".to_string(),
+                before: 0.2,
+                after: 0.25,
+            }],
+        };
+        assert!(delta.prove_non_relaxing().is_err());
+    }
+}
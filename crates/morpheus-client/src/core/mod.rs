@@ -0,0 +1,4 @@
+//! Decision engines: reconciliation of evolution proposals and Errority tightening
+
+pub mod errority;
+pub mod reconciliation;
@@ -5,12 +5,15 @@
 
 use crate::types::{
     audit::{EvolutionAuditRecord, EvolutionOutcome},
-    corridor::EcoCorridorContext,
+    consent::{unmet_conditions, FpicConsent, RevocationEvent},
+    corridor::{CorridorId, EcoCorridorContext, FpicIdsStatus},
     evidence::EvidenceBundle,
     guards::{BciCeilingGuard, EnvelopeGuard, GuardDecision, RoHGuard},
     policy::PolicyProfile,
 };
 use crate::MorpheusError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::sync::Arc;
 use tracing::{debug, error, info, warn};
 
@@ -24,6 +27,9 @@ pub struct EvolutionProposal {
     pub evidence_bundle: EvidenceBundle,
     /// Description of the neuromorphic decision
     pub neuromorphic_decision: String,
+    /// Operation class matched against `operations=` consent conditions
+    /// (e.g. "monitoring", "actuation")
+    pub operation: String,
     /// Current BCI* value
     pub current_bci: f64,
     /// Proposed BCI* after evolution
@@ -42,6 +48,43 @@ pub struct EvolutionProposal {
     pub proposed_session_length: u32,
 }
 
+/// Lifecycle status of a proposal tracked by the engine
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ProposalStatus {
+    /// Submitted, not yet evaluated
+    Pending,
+    /// Evaluated and allowed; carries the audit record ID
+    Allowed(String),
+    /// Evaluated and rejected
+    Rejected(String),
+    /// Invalidated before evaluation (e.g., FPIC revoked)
+    Invalidated(String),
+    /// Previously allowed, now flagged for human review (e.g., FPIC revoked)
+    UnderReview(String),
+}
+
+/// A proposal tracked across its lifecycle
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TrackedProposal {
+    /// Caller-assigned proposal ID
+    pub proposal_id: String,
+    /// Corridor the proposal operates in
+    pub corridor_id: CorridorId,
+    /// DID of the proposer
+    pub did: String,
+    /// Current status
+    pub status: ProposalStatus,
+}
+
+/// Effect of propagating an FPIC revocation
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RevocationImpact {
+    /// Pending proposals that were invalidated
+    pub invalidated: Vec<String>,
+    /// Previously allowed proposals now under review
+    pub flagged_for_review: Vec<String>,
+}
+
 /// The reconciliation engine
 pub struct ReconciliationEngine {
     /// Active policy profile
@@ -52,6 +95,10 @@ pub struct ReconciliationEngine {
     pub roh_ceiling: f64,
     /// Envelope guard (instantiated per proposal)
     pub envelope_guard_enabled: bool,
+    /// Proposals submitted through [`ReconciliationEngine::submit`]
+    pub proposals: Vec<TrackedProposal>,
+    /// Corridors whose FPIC revocation has been propagated
+    pub revoked_corridors: HashSet<CorridorId>,
 }
 
 impl ReconciliationEngine {
@@ -67,6 +114,8 @@ impl ReconciliationEngine {
             bci_guard: BciCeilingGuard::new(bci_ceiling, warn_threshold),
             roh_ceiling: 0.3, // Hard constitutional ceiling
             envelope_guard_enabled: true,
+            proposals: Vec::new(),
+            revoked_corridors: HashSet::new(),
         })
     }
 
@@ -85,6 +134,25 @@ impl ReconciliationEngine {
             .corridor_context
             .validate()
             .map_err(|e| MorpheusError::CorridorViolation(e))?;
+        let corridor_id = &proposal.corridor_context.corridor_id;
+        if self.revoked_corridors.contains(corridor_id) {
+            return Err(MorpheusError::CorridorViolation(format!(
+                "FPIC/IDS consent revoked for corridor {corridor_id}"
+            )));
+        }
+        // Anything but `Granted` or fully met `Conditional` consent is unmet.
+        let unmet = unmet_conditions(
+            &proposal.corridor_context,
+            &proposal.operation,
+            chrono::Utc::now(),
+        )
+        .map_err(|e| MorpheusError::CorridorViolation(e.to_string()))?;
+        if !unmet.is_empty() {
+            return Err(MorpheusError::CorridorViolation(format!(
+                "FPIC conditions not met: {}",
+                unmet.join(", ")
+            )));
+        }
 
         // Step 2: Validate evidence bundle
         proposal
@@ -170,6 +238,92 @@ impl ReconciliationEngine {
         Ok((EvolutionOutcome::Allowed, audit_record))
     }
 
+    /// Track a proposal as pending
+    pub fn submit(&mut self, proposal_id: String, proposal: &EvolutionProposal) {
+        self.proposals.push(TrackedProposal {
+            proposal_id,
+            corridor_id: proposal.corridor_context.corridor_id.clone(),
+            did: proposal.did.clone(),
+            status: ProposalStatus::Pending,
+        });
+    }
+
+    /// Evaluate a tracked pending proposal and record its outcome
+    pub fn evaluate_tracked(
+        &mut self,
+        proposal_id: &str,
+        proposal: &EvolutionProposal,
+    ) -> Result<(EvolutionOutcome, EvolutionAuditRecord), MorpheusError> {
+        let index = self
+            .proposals
+            .iter()
+            .position(|p| p.proposal_id == proposal_id)
+            .ok_or_else(|| MorpheusError::Unknown(format!("untracked proposal {proposal_id}")))?;
+        if self.proposals[index].status != ProposalStatus::Pending {
+            return Err(MorpheusError::CorridorViolation(format!(
+                "proposal {proposal_id} is {:?}",
+                self.proposals[index].status
+            )));
+        }
+        let result = self.evaluate_evolution(proposal);
+        self.proposals[index].status = match &result {
+            Ok((_, record)) => ProposalStatus::Allowed(record.record_id.clone()),
+            Err(e) => ProposalStatus::Rejected(e.to_string()),
+        };
+        result
+    }
+
+    /// Propagate an FPIC revocation: invalidate pending proposals in the
+    /// corridor, flag allowed ones for review, and refuse new evaluations
+    /// there until [`ReconciliationEngine::clear_revocation`] is called.
+    pub fn apply_revocation(&mut self, event: &RevocationEvent) -> RevocationImpact {
+        warn!(
+            "FPIC revoked for corridor {} by {}",
+            event.corridor_id, event.revoked_by
+        );
+        self.revoked_corridors.insert(event.corridor_id.clone());
+        let note = format!(
+            "FPIC revoked by {} at {}",
+            event.revoked_by,
+            event.revoked_at.to_rfc3339()
+        );
+        let mut impact = RevocationImpact::default();
+        for tracked in self
+            .proposals
+            .iter_mut()
+            .filter(|p| p.corridor_id == event.corridor_id)
+        {
+            match tracked.status {
+                ProposalStatus::Pending => {
+                    tracked.status = ProposalStatus::Invalidated(note.clone());
+                    impact.invalidated.push(tracked.proposal_id.clone());
+                }
+                ProposalStatus::Allowed(_) => {
+                    tracked.status = ProposalStatus::UnderReview(note.clone());
+                    impact.flagged_for_review.push(tracked.proposal_id.clone());
+                }
+                _ => {}
+            }
+        }
+        impact
+    }
+
+    /// Lift a propagated revocation; `consent` must show the corridor's
+    /// consent re-granted, with or without conditions
+    pub fn clear_revocation(&mut self, consent: &FpicConsent) -> Result<(), MorpheusError> {
+        if !matches!(
+            consent.status,
+            FpicIdsStatus::Granted | FpicIdsStatus::Conditional(_)
+        ) {
+            return Err(MorpheusError::CorridorViolation(format!(
+                "FPIC for corridor {} is {:?}, not re-granted",
+                consent.corridor_id, consent.status
+            )));
+        }
+        self.revoked_corridors.remove(&consent.corridor_id);
+        Ok(())
+    }
+
     /// Update the active policy profile
     pub fn set_policy_profile(&mut self, profile: PolicyProfile) -> Result<(), MorpheusError> {
         profile.validate().map_err(|e| MorpheusError::PolicyError(e))?;
@@ -211,6 +365,7 @@ mod tests {
             corridor_context: corridor,
             evidence_bundle: evidence,
             neuromorphic_decision: "test".to_string(),
+            operation: "monitoring".to_string(),
             current_bci: 0.1,
             proposed_bci: 0.15,
             current_roh: 0.1,
@@ -224,4 +379,97 @@ mod tests {
         let result = engine.evaluate_evolution(&proposal);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_revocation_invalidates_and_flags() {
+        let profile = PolicyProfile::new("test".to_string(), "1.0".to_string(), "test".to_string());
+        let mut engine = ReconciliationEngine::new(profile).unwrap();
+
+        let mut corridor = EcoCorridorContext::new("gric".to_string(), "GRIC".to_string());
+        corridor.jurisdictions.push("US/Arizona".to_string());
+        corridor.fpic_ids_status = FpicIdsStatus::Granted;
+        corridor.eco_impact.corridor_safety = 0.9;
+        let mut evidence = EvidenceBundle::new("ev1".to_string(), 0.9, 0.1);
+        evidence.add_tag(crate::types::evidence::BiophysicalDomains::atp());
+        let proposal = |decision: &str| EvolutionProposal {
+            did: "did:bostrom:test".to_string(),
+            corridor_context: corridor.clone(),
+            evidence_bundle: evidence.clone(),
+            neuromorphic_decision: decision.to_string(),
+            operation: "monitoring".to_string(),
+            current_bci: 0.1,
+            proposed_bci: 0.1,
+            current_roh: 0.1,
+            proposed_roh: 0.1,
+            current_duty_cycle: 0.5,
+            proposed_duty_cycle: 0.4,
+            current_session_length: 60,
+            proposed_session_length: 45,
+        };
+
+        let first = proposal("first");
+        engine.submit("p1".to_string(), &first);
+        engine.evaluate_tracked("p1", &first).unwrap();
+        engine.submit("p2".to_string(), &proposal("second"));
+
+        let mut consent = FpicConsent::new("gric".to_string(), true);
+        let now = chrono::Utc::now();
+        consent.transition(FpicIdsStatus::Pending, "did:council", now, None).unwrap();
+        consent.transition(FpicIdsStatus::Granted, "did:council", now, None).unwrap();
+        let event = consent
+            .transition(FpicIdsStatus::Revoked, "did:council", now, None)
+            .unwrap()
+            .unwrap();
+
+        let impact = engine.apply_revocation(&event);
+        assert_eq!(impact.invalidated, vec!["p2".to_string()]);
+        assert_eq!(impact.flagged_for_review, vec!["p1".to_string()]);
+        assert!(engine.evaluate_evolution(&proposal("third")).is_err());
+
+        // Still revoked: the revocation stays in force.
+        assert!(engine.clear_revocation(&consent).is_err());
+        assert!(engine.evaluate_evolution(&proposal("third")).is_err());
+        consent.transition(FpicIdsStatus::Pending, "did:council", now, None).unwrap();
+        consent.transition(FpicIdsStatus::Granted, "did:council", now, None).unwrap();
+        engine.clear_revocation(&consent).unwrap();
+        assert!(engine.evaluate_evolution(&proposal("third")).is_ok());
+    }
+
+    #[test]
+    fn test_fpic_gates_on_status_and_operation() {
+        let profile = PolicyProfile::new("test".to_string(), "1.0".to_string(), "test".to_string());
+        let engine = ReconciliationEngine::new(profile).unwrap();
+
+        let mut corridor = EcoCorridorContext::new("gric".to_string(), "GRIC".to_string());
+        corridor.jurisdictions.push("US/Arizona".to_string());
+        corridor.eco_impact.corridor_safety = 0.9;
+        let mut evidence = EvidenceBundle::new("ev1".to_string(), 0.9, 0.1);
+        evidence.add_tag(crate::types::evidence::BiophysicalDomains::atp());
+        let mut proposal = EvolutionProposal {
+            did: "did:bostrom:test".to_string(),
+            corridor_context: corridor,
+            evidence_bundle: evidence,
+            neuromorphic_decision: "monitoring of actuation telemetry".to_string(),
+            operation: "actuation".to_string(),
+            current_bci: 0.1,
+            proposed_bci: 0.1,
+            current_roh: 0.1,
+            proposed_roh: 0.1,
+            current_duty_cycle: 0.5,
+            proposed_duty_cycle: 0.4,
+            current_session_length: 60,
+            proposed_session_length: 45,
+        };
+
+        for status in [FpicIdsStatus::NotObtained, FpicIdsStatus::Pending] {
+            proposal.corridor_context.fpic_ids_status = status;
+            assert!(engine.evaluate_evolution(&proposal).is_err());
+        }
+        // The decision text mentions "monitoring", but the operation does not match.
+        proposal.corridor_context.fpic_ids_status =
+            FpicIdsStatus::Conditional(vec!["operations=monitoring".to_string()]);
+        assert!(engine.evaluate_evolution(&proposal).is_err());
+        proposal.operation = "monitoring".to_string();
+        assert!(engine.evaluate_evolution(&proposal).is_ok());
+    }
 }
@@ -16,6 +16,7 @@
 pub mod aln;
 pub mod bostrom;
 pub mod core;
+pub mod nanoswarm;
 pub mod telemetry;
 pub mod types;
 
@@ -76,6 +76,7 @@ fn main() -> Result<()> {
         evidence_bundle: evidence,
         neuromorphic_decision: "Enable advanced brain-computer interface with somatosensory feedback"
             .to_string(),
+        operation: "actuation".to_string(),
         current_bci: 0.12,
         proposed_bci: 0.18,
         current_roh: 0.10,
@@ -1,23 +1,44 @@
-use crate::types::guards::{GuardDecision, SafetyGuard};
+//! Integrity limits for nanoswarm activity inside host microspaces
+
+use crate::types::guards::GuardDecision;
 use serde::{Deserialize, Serialize};
 
+/// Density ceiling (%) for organisms without an explicit entry
+pub const DEFAULT_DENSITY_CEILING_PCT: f64 = 0.1;
+/// Activity power limit (mW) for ecosystem roles without an explicit entry
+pub const DEFAULT_ACTIVITY_POWER_LIMIT_MW: f64 = 1.0;
+/// Occupancy limit (secs) for organisms without an explicit entry
+pub const DEFAULT_OCCUPANCY_LIMIT_SECS: u64 = 3600;
+
+/// Observed state of one host microspace
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MicrospaceState {
+    /// Microspace identifier
     pub microspace_id: String,
+    /// Organism (tissue) occupying the microspace
     pub occupant_organism: String,
+    /// Microspace volume (mm³)
     pub volume_mm3: f64,
+    /// Volume currently taken by the swarm (mm³)
     pub current_swarm_volume_mm3: f64,
+    /// Ecosystem role of the occupant
     pub ecosystem_role: String,
 }
 
+/// Activity a swarm proposes to run inside a microspace
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SwarmActivityProposal {
+    /// Microspace the activity runs in
     pub target_microspace_id: String,
+    /// Proposed power draw (mW)
     pub proposed_energy_draw_mw: f64,
+    /// Proposed occupancy (secs)
     pub proposed_duration_secs: u64,
+    /// Kind of activity
     pub activity_type: String,
 }
 
+/// Density, power and occupancy limits per organism and ecosystem role
 pub struct MicrospaceIntegrityGuard {
     /// Max density for each organism type (evidence-backed, %)
     pub density_ceilings: std::collections::HashMap<String, f64>,
@@ -27,7 +48,14 @@ pub struct MicrospaceIntegrityGuard {
     pub occupancy_limits: std::collections::HashMap<String, u64>,
 }
 
+impl Default for MicrospaceIntegrityGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MicrospaceIntegrityGuard {
+    /// Guard with the evidence-backed default limits
     pub fn new() -> Self {
         let mut density_ceilings = std::collections::HashMap::new();
         density_ceilings.insert("soil_rhizosphere".to_string(), 0.5);
@@ -53,12 +81,13 @@ impl MicrospaceIntegrityGuard {
         }
     }
 
+    /// Gate the swarm's current density against the occupant's ceiling
     pub fn evaluate_density(&self, state: &MicrospaceState) -> GuardDecision {
         let ceiling = self
             .density_ceilings
             .get(&state.occupant_organism)
             .copied()
-            .unwrap_or(0.1);
+            .unwrap_or(DEFAULT_DENSITY_CEILING_PCT);
 
         let current_density_pct = (state.current_swarm_volume_mm3 / state.volume_mm3) * 100.0;
 
@@ -77,6 +106,7 @@ impl MicrospaceIntegrityGuard {
         }
     }
 
+    /// Gate a proposal's power draw against the ecosystem role's limit
     pub fn evaluate_activity(
         &self,
         state: &MicrospaceState,
@@ -86,7 +116,7 @@ impl MicrospaceIntegrityGuard {
             .activity_power_limits
             .get(&state.ecosystem_role)
             .copied()
-            .unwrap_or(1.0);
+            .unwrap_or(DEFAULT_ACTIVITY_POWER_LIMIT_MW);
 
         if proposal.proposed_energy_draw_mw > limit {
             GuardDecision::Forbid(format!(
@@ -97,14 +127,15 @@ impl MicrospaceIntegrityGuard {
                 state.ecosystem_role
             ))
         } else if proposal.proposed_energy_draw_mw > limit * 0.7 {
-            GuardDecision::PauseAndRest(format!(
-                "Activity power approaching limit; consider reducing duty cycle"
-            ))
+            GuardDecision::PauseAndRest(
+                "Activity power approaching limit; consider reducing duty cycle".to_string(),
+            )
         } else {
             GuardDecision::AllowFull
         }
     }
 
+    /// Gate a proposal's occupancy against the occupant's limit
     pub fn evaluate_duration(
         &self,
         state: &MicrospaceState,
@@ -114,7 +145,7 @@ impl MicrospaceIntegrityGuard {
             .occupancy_limits
             .get(&state.occupant_organism)
             .copied()
-            .unwrap_or(3600);
+            .unwrap_or(DEFAULT_OCCUPANCY_LIMIT_SECS);
 
         if proposal.proposed_duration_secs > limit {
             GuardDecision::Forbid(format!(
@@ -122,14 +153,13 @@ impl MicrospaceIntegrityGuard {
                 proposal.proposed_duration_secs, limit, state.occupant_organism
             ))
         } else if proposal.proposed_duration_secs > (limit as f64 * 0.8) as u64 {
-            GuardDecision::PauseAndRest(format!(
-                "Occupancy near limit; consider retreat soon"
-            ))
+            GuardDecision::PauseAndRest("Occupancy near limit; consider retreat soon".to_string())
         } else {
             GuardDecision::AllowFull
         }
     }
 
+    /// Run all three gates; any `Forbid` wins, then any caution
     pub fn evaluate_swarm_proposal(
         &self,
         state: &MicrospaceState,
@@ -177,7 +207,7 @@ mod tests {
             microspace_id: "soil_001".to_string(),
             occupant_organism: "soil_rhizosphere".to_string(),
             volume_mm3: 1000.0,
-            current_swarm_volume_mm3: 10.0, // 1% density
+            current_swarm_volume_mm3: 3.0, // 0.3% density (under 0.5% ceiling)
             ecosystem_role: "nutrient_cycling".to_string(),
         };
         let result = guard.evaluate_density(&state);
@@ -0,0 +1,3 @@
+//! Nanoswarm microspace integrity guards
+
+pub mod microspace_guard;
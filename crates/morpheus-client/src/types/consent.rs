@@ -0,0 +1,337 @@
+//! FPIC/IDS consent lifecycle
+//!
+//! Enforces the allowed transitions between `FpicIdsStatus` states, records who
+//! decided each transition and when, turns `Conditional` strings into evaluable
+//! conditions, and emits a revocation event that the reconciliation engine uses
+//! to invalidate pending proposals and flag allowed operations for review.
+
+use crate::types::corridor::{CorridorId, EcoCorridorContext, FpicIdsStatus};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Errors raised by the consent lifecycle
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum ConsentError {
+    /// The requested status change is not a legal FPIC transition
+    #[error("invalid FPIC transition {from:?} -> {to:?}")]
+    InvalidTransition {
+        /// Status before the attempted transition
+        from: FpicIdsStatus,
+        /// Status that was requested
+        to: FpicIdsStatus,
+    },
+    /// A transition must name the deciding party
+    #[error("FPIC decision must record the deciding DID")]
+    MissingDecider,
+    /// A decision cannot be dated before the previous one
+    #[error("FPIC decision at {0} predates the previous decision")]
+    OutOfOrder(DateTime<Utc>),
+    /// Consent was revoked but the corridor marks it as non-revocable
+    #[error("FPIC consent for this corridor is not revocable")]
+    NotRevocable,
+    /// A `Conditional` entry could not be parsed into an evaluable condition
+    #[error("unrecognized consent condition: {0}")]
+    UnknownCondition(String),
+}
+
+/// An evaluable condition attached to `Conditional` consent.
+///
+/// Text form (as stored in `FpicIdsStatus::Conditional`):
+/// `valid_from=<rfc3339>`, `valid_until=<rfc3339>`, `jurisdiction=<name>`,
+/// `max_composite_risk=<0..1>`, `operations=<a>|<b>|...`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub enum ConsentCondition {
+    /// Consent applies only from this instant
+    ValidFrom(DateTime<Utc>),
+    /// Consent lapses at this instant
+    ValidUntil(DateTime<Utc>),
+    /// Corridor must operate under this jurisdiction
+    Jurisdiction(String),
+    /// Corridor composite eco risk must not exceed this value
+    MaxCompositeRisk(f64),
+    /// Only these operation classes are consented to
+    Operations(Vec<String>),
+}
+
+impl ConsentCondition {
+    /// Parse the text form of a condition
+    pub fn parse(raw: &str) -> Result<Self, ConsentError> {
+        let unknown = || ConsentError::UnknownCondition(raw.to_string());
+        let (key, value) = raw.split_once('=').ok_or_else(unknown)?;
+        let value = value.trim();
+        let instant = |v: &str| {
+            DateTime::parse_from_rfc3339(v)
+                .map(|t| t.with_timezone(&Utc))
+                .map_err(|_| unknown())
+        };
+        match key.trim() {
+            "valid_from" => Ok(Self::ValidFrom(instant(value)?)),
+            "valid_until" => Ok(Self::ValidUntil(instant(value)?)),
+            "jurisdiction" if !value.is_empty() => Ok(Self::Jurisdiction(value.to_string())),
+            "max_composite_risk" => value
+                .parse::<f64>()
+                .ok()
+                .filter(|x| (0.0..=1.0).contains(x))
+                .map(Self::MaxCompositeRisk)
+                .ok_or_else(unknown),
+            "operations" => {
+                let ops: Vec<String> = value
+                    .split('|')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                if ops.is_empty() {
+                    Err(unknown())
+                } else {
+                    Ok(Self::Operations(ops))
+                }
+            }
+            _ => Err(unknown()),
+        }
+    }
+
+    /// Text form, the inverse of [`ConsentCondition::parse`]
+    pub fn to_text(&self) -> String {
+        match self {
+            Self::ValidFrom(t) => format!("valid_from={}", t.to_rfc3339()),
+            Self::ValidUntil(t) => format!("valid_until={}", t.to_rfc3339()),
+            Self::Jurisdiction(j) => format!("jurisdiction={j}"),
+            Self::MaxCompositeRisk(r) => format!("max_composite_risk={r}"),
+            Self::Operations(ops) => format!("operations={}", ops.join("|")),
+        }
+    }
+
+    /// Check the condition for one operation in a corridor at `now`
+    pub fn is_satisfied(
+        &self,
+        corridor: &EcoCorridorContext,
+        operation: &str,
+        now: DateTime<Utc>,
+    ) -> bool {
+        match self {
+            Self::ValidFrom(t) => now >= *t,
+            Self::ValidUntil(t) => now < *t,
+            Self::Jurisdiction(j) => corridor.jurisdictions.iter().any(|c| c == j),
+            Self::MaxCompositeRisk(r) => corridor.eco_impact.composite_risk() <= *r,
+            Self::Operations(ops) => ops.iter().any(|o| o == operation),
+        }
+    }
+}
+
+/// Evaluate the consent carried by a corridor for one operation.
+///
+/// Returns the unmet conditions (in text form); `Granted` has none, and every
+/// status other than `Granted`/`Conditional` is reported as a single failure.
+pub fn unmet_conditions(
+    corridor: &EcoCorridorContext,
+    operation: &str,
+    now: DateTime<Utc>,
+) -> Result<Vec<String>, ConsentError> {
+    match &corridor.fpic_ids_status {
+        FpicIdsStatus::Granted => Ok(Vec::new()),
+        FpicIdsStatus::Conditional(raw) => {
+            let mut unmet = Vec::new();
+            for text in raw {
+                if !ConsentCondition::parse(text)?.is_satisfied(corridor, operation, now) {
+                    unmet.push(text.clone());
+                }
+            }
+            Ok(unmet)
+        }
+        other => Ok(vec![format!("FPIC status is {other:?}")]),
+    }
+}
+
+/// One recorded consent decision
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ConsentTransition {
+    /// Status before the decision
+    pub from: FpicIdsStatus,
+    /// Status after the decision
+    pub to: FpicIdsStatus,
+    /// DID of the deciding party (community council, data steward, ...)
+    pub decided_by: String,
+    /// When the decision was taken
+    pub decided_at: DateTime<Utc>,
+    /// Optional free-text rationale
+    pub reason: Option<String>,
+}
+
+/// Emitted when consent for a corridor is revoked
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct RevocationEvent {
+    /// Corridor whose consent was revoked
+    pub corridor_id: CorridorId,
+    /// DID that revoked consent
+    pub revoked_by: String,
+    /// When consent was revoked
+    pub revoked_at: DateTime<Utc>,
+    /// Optional rationale
+    pub reason: Option<String>,
+}
+
+/// Consent lifecycle for one corridor
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FpicConsent {
+    /// Corridor this consent applies to
+    pub corridor_id: CorridorId,
+    /// Whether consent may be revoked once given
+    pub revocable: bool,
+    /// Current status
+    pub status: FpicIdsStatus,
+    /// All decisions, oldest first
+    pub history: Vec<ConsentTransition>,
+}
+
+impl FpicConsent {
+    /// Start a lifecycle in `NotObtained`
+    pub fn new(corridor_id: CorridorId, revocable: bool) -> Self {
+        Self {
+            corridor_id,
+            revocable,
+            status: FpicIdsStatus::NotObtained,
+            history: Vec::new(),
+        }
+    }
+
+    /// Whether `from -> to` is a legal FPIC transition
+    pub fn is_valid_transition(from: &FpicIdsStatus, to: &FpicIdsStatus) -> bool {
+        use FpicIdsStatus::*;
+        matches!(
+            (from, to),
+            (NotObtained, Pending)
+                | (Pending, Granted)
+                | (Pending, Conditional(_))
+                | (Pending, NotObtained)
+                | (Granted, Conditional(_))
+                | (Granted, Revoked)
+                | (Conditional(_), Granted)
+                | (Conditional(_), Conditional(_))
+                | (Conditional(_), Revoked)
+                | (Revoked, Pending)
+        )
+    }
+
+    /// Apply a decision. Conditions in a `Conditional` target must parse.
+    /// Returns a [`RevocationEvent`] when the decision revokes consent.
+    pub fn transition(
+        &mut self,
+        to: FpicIdsStatus,
+        decided_by: &str,
+        decided_at: DateTime<Utc>,
+        reason: Option<String>,
+    ) -> Result<Option<RevocationEvent>, ConsentError> {
+        if decided_by.trim().is_empty() {
+            return Err(ConsentError::MissingDecider);
+        }
+        if !Self::is_valid_transition(&self.status, &to) {
+            return Err(ConsentError::InvalidTransition {
+                from: self.status.clone(),
+                to,
+            });
+        }
+        if let Some(last) = self.history.last() {
+            if decided_at < last.decided_at {
+                return Err(ConsentError::OutOfOrder(decided_at));
+            }
+        }
+        if to == FpicIdsStatus::Revoked && !self.revocable {
+            return Err(ConsentError::NotRevocable);
+        }
+        if let FpicIdsStatus::Conditional(raw) = &to {
+            for text in raw {
+                ConsentCondition::parse(text)?;
+            }
+        }
+
+        self.history.push(ConsentTransition {
+            from: self.status.clone(),
+            to: to.clone(),
+            decided_by: decided_by.to_string(),
+            decided_at,
+            reason: reason.clone(),
+        });
+        self.status = to;
+
+        Ok((self.status == FpicIdsStatus::Revoked).then(|| RevocationEvent {
+            corridor_id: self.corridor_id.clone(),
+            revoked_by: decided_by.to_string(),
+            revoked_at: decided_at,
+            reason,
+        }))
+    }
+
+    /// Most recent decision, if any
+    pub fn last_decision(&self) -> Option<&ConsentTransition> {
+        self.history.last()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(h: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 5, 1, h, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_valid_lifecycle_emits_revocation() {
+        let mut consent = FpicConsent::new("gric".to_string(), true);
+        assert!(consent
+            .transition(FpicIdsStatus::Pending, "did:council", at(1), None)
+            .unwrap()
+            .is_none());
+        consent
+            .transition(FpicIdsStatus::Granted, "did:council", at(2), None)
+            .unwrap();
+        let event = consent
+            .transition(FpicIdsStatus::Revoked, "did:council", at(3), Some("spill".into()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.corridor_id, "gric");
+        assert_eq!(consent.history.len(), 3);
+        assert_eq!(consent.last_decision().unwrap().decided_by, "did:council");
+    }
+
+    #[test]
+    fn test_invalid_transitions_rejected() {
+        let mut consent = FpicConsent::new("gric".to_string(), false);
+        assert!(matches!(
+            consent.transition(FpicIdsStatus::Granted, "did:council", at(1), None),
+            Err(ConsentError::InvalidTransition { .. })
+        ));
+        consent
+            .transition(FpicIdsStatus::Pending, "did:council", at(2), None)
+            .unwrap();
+        assert_eq!(
+            consent.transition(FpicIdsStatus::Granted, "did:council", at(1), None),
+            Err(ConsentError::OutOfOrder(at(1)))
+        );
+        consent
+            .transition(FpicIdsStatus::Granted, "did:council", at(3), None)
+            .unwrap();
+        assert_eq!(
+            consent.transition(FpicIdsStatus::Revoked, "did:council", at(4), None),
+            Err(ConsentError::NotRevocable)
+        );
+    }
+
+    #[test]
+    fn test_conditions_are_evaluated() {
+        let mut corridor = EcoCorridorContext::new("gric".to_string(), "GRIC".to_string());
+        corridor.jurisdictions.push("US/Arizona".to_string());
+        corridor.fpic_ids_status = FpicIdsStatus::Conditional(vec![
+            "jurisdiction=US/Arizona".to_string(),
+            "operations=monitoring|telemetry".to_string(),
+            format!("valid_until={}", at(12).to_rfc3339()),
+        ]);
+        assert!(unmet_conditions(&corridor, "monitoring", at(10)).unwrap().is_empty());
+        assert_eq!(unmet_conditions(&corridor, "actuation", at(13)).unwrap().len(), 2);
+
+        corridor.fpic_ids_status = FpicIdsStatus::Conditional(vec!["be nice".to_string()]);
+        assert!(unmet_conditions(&corridor, "monitoring", at(10)).is_err());
+    }
+}
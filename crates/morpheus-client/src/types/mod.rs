@@ -0,0 +1,8 @@
+//! Core data types: corridors, consent, evidence, guards, policies and audit records
+
+pub mod audit;
+pub mod consent;
+pub mod corridor;
+pub mod evidence;
+pub mod guards;
+pub mod policy;
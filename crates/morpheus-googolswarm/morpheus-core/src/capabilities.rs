@@ -1,30 +1,65 @@
+use reversal_policy::{ReversalInputs, ReversalPolicy};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum CapabilityTier {
     Alpha,
     Beta,
     Stable,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CapabilityState {
     pub tier: CapabilityTier,
     pub can_self_modify: bool,
     pub can_request_transition: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ReversalConditions {
     pub neuromorph_god_satisfied: bool,
     pub explicit_reversal_order: bool,
     pub no_safer_alternative: bool,
+    #[serde(default)]
+    pub life_harm_flag: bool,
 }
 
 impl ReversalConditions {
+    /// Evaluated by the shared policy in `aln/reversal_policy.aln`.
     pub fn permits_downgrade(&self) -> bool {
-        self.neuromorph_god_satisfied
-            && self.explicit_reversal_order
-            && self.no_safer_alternative
+        ReversalPolicy::standard()
+            .evaluate(&ReversalInputs {
+                life_harm_flag: self.life_harm_flag,
+                neuromorph_god_satisfied: self.neuromorph_god_satisfied,
+                explicit_reversal_order: self.explicit_reversal_order,
+                no_safer_alternative: self.no_safer_alternative,
+            })
+            .is_granted()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permits_downgrade_agrees_with_shared_policy() {
+        for bits in 0u8..16 {
+            let cond = ReversalConditions {
+                neuromorph_god_satisfied: bits & 1 != 0,
+                explicit_reversal_order: bits & 2 != 0,
+                no_safer_alternative: bits & 4 != 0,
+                life_harm_flag: bits & 8 != 0,
+            };
+            let legacy = cond.neuromorph_god_satisfied
+                && cond.explicit_reversal_order
+                && cond.no_safer_alternative;
+            assert_eq!(
+                cond.permits_downgrade(),
+                legacy && !cond.life_harm_flag,
+                "{cond:?}"
+            );
+        }
     }
 }
@@ -1,6 +1,7 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum SpeciesKind {
     Human,
     Synthetic,
@@ -8,7 +9,7 @@ pub enum SpeciesKind {
     Other(String),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BiophysicalEnvelope {
     pub species: SpeciesKind,
     pub min_safe_roh: f32,
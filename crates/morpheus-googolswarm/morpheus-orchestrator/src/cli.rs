@@ -18,6 +18,11 @@ pub enum Commands {
         #[arg(short, long)]
         spec: Option<String>,
     },
+    /// Emit the JSON Schema describing the governance profile produced by `eval`
+    Schema {
+        #[arg(short, long)]
+        out: Option<String>,
+    },
 }
 
 pub fn run() -> Result<()> {
@@ -30,6 +35,13 @@ pub fn run() -> Result<()> {
             let json = serde_json::to_string_pretty(&profile)?;
             println!("{json}");
         }
+        Commands::Schema { out } => {
+            let json = morpheus_spec_aln::governance_profile_schema_json();
+            match out {
+                Some(path) => std::fs::write(path, json)?,
+                None => print!("{json}"),
+            }
+        }
     }
     Ok(())
 }
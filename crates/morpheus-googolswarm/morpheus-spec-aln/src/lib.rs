@@ -1,6 +1,8 @@
 pub mod aln;
 pub mod model;
 pub mod parser;
+pub mod schema;
 
 pub use crate::model::{GovernanceProfile, ParsedError};
 pub use crate::parser::{parse_aln, to_governance_profile};
+pub use crate::schema::{governance_profile_schema, governance_profile_schema_json};
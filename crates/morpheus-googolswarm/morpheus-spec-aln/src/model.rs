@@ -1,19 +1,23 @@
 use morpheus_core::capabilities::CapabilityState;
 use morpheus_core::rights::RightsLedgerEntry;
 use morpheus_core::species::BiophysicalEnvelope;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
-#[derive(Debug)]
+#[derive(Debug, Error)]
 pub enum ParsedError {
+    #[error("invalid ALN spec: {0}")]
     Invalid(String),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum ParsedSection {
     Morpheus,
     Other(String),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum ParsedRoleKind {
     NeuromorphGod,
     Host,
@@ -33,32 +37,32 @@ impl ParsedRoleKind {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SpeciesProfile {
     pub envelope: BiophysicalEnvelope,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum ReversalPermission {
     DisallowNeuromorphReversal,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ReversalSettings {
     pub permission: ReversalPermission,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ReversalPolicyProfile {
     pub settings: ReversalSettings,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ParsedDocument {
     pub raw: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct GovernanceProfile {
     pub section: ParsedSection,
     pub document: ParsedDocument,
@@ -0,0 +1,45 @@
+use crate::model::GovernanceProfile;
+use schemars::Schema;
+
+pub const GOVERNANCE_PROFILE_SCHEMA_ID: &str =
+    "https://morpheus.aln/schemas/governance-profile.schema.json";
+
+/// JSON Schema (draft 2020-12) for the profile emitted by `morpheus-orchestrator eval`.
+pub fn governance_profile_schema() -> Schema {
+    let mut schema = schemars::schema_for!(GovernanceProfile);
+    schema.insert("$id".to_string(), GOVERNANCE_PROFILE_SCHEMA_ID.into());
+    schema
+}
+
+pub fn governance_profile_schema_json() -> String {
+    let mut json = serde_json::to_string_pretty(&governance_profile_schema())
+        .expect("schema is always serializable");
+    json.push('\n');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::to_governance_profile;
+
+    #[test]
+    fn checked_in_schema_matches_model() {
+        let checked_in = include_str!("../../../../schemas/governance-profile.schema.json");
+        assert_eq!(
+            checked_in,
+            governance_profile_schema_json(),
+            "regenerate with `morpheus-orchestrator schema --out schemas/governance-profile.schema.json`"
+        );
+    }
+
+    #[test]
+    fn profile_round_trips_through_json() {
+        let spec = "SECTION=MORPHEUS\nROLE=NEUROMORPH_GOD\n";
+        let profile = to_governance_profile(spec).unwrap();
+        let json = serde_json::to_string(&profile).unwrap();
+        let back: GovernanceProfile = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.document.raw, spec);
+        assert_eq!(back.species_profile.envelope.max_safe_roh, 0.30);
+    }
+}
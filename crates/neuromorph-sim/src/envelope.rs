@@ -1,5 +1,9 @@
 use serde::{Deserialize, Serialize};
 
+/// RoH at or below this is safe. This is the ceiling mitigations aim for,
+/// not the reversal policy's `roh_min`, which triggers `nosaferalternative`.
+pub const SAFE_ROH_CEILING: f64 = 0.3;
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EnvelopeSnapshot {
     pub roh: f64,
@@ -1,7 +1,16 @@
 mod envelope;
 mod mitigation;
+mod monte_carlo;
+mod planner;
 mod projector;
 
-pub use envelope::EnvelopeSnapshot;
-pub use mitigation::Mitigation;
-pub use projector::{reproject_non_reversal, nosaferalternative};
+pub use envelope::{EnvelopeSnapshot, SAFE_ROH_CEILING};
+pub use mitigation::{EffectModel, Mitigation, MitigationEffect};
+pub use monte_carlo::{
+    simulate, Drift, MonteCarloConfig, MonteCarloReport, NoiseModel, PercentileBand,
+    SimulationError, StepBands,
+};
+pub use planner::{
+    MitigationPlan, MitigationPlanner, NoSaferAlternativeProof, PlanOutcome, PlannerError,
+};
+pub use projector::{nosaferalternative, reproject_non_reversal, reproject_with_model};
@@ -1,9 +1,110 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+use crate::EnvelopeSnapshot;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum Mitigation {
     TightenEnvelopes,
     PauseOperations,
     InduceRest,
     Custom(String),
 }
+
+impl Mitigation {
+    /// Key used to look the mitigation up in an [`EffectModel`].
+    pub fn name(&self) -> &str {
+        match self {
+            Mitigation::TightenEnvelopes => "TightenEnvelopes",
+            Mitigation::PauseOperations => "PauseOperations",
+            Mitigation::InduceRest => "InduceRest",
+            Mitigation::Custom(name) => name,
+        }
+    }
+}
+
+/// Effect of applying a mitigation once.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct MitigationEffect {
+    #[serde(default = "one")]
+    pub roh_factor: f64,
+    #[serde(default)]
+    pub decay_delta: f64,
+    #[serde(default)]
+    pub lifeforce_delta: f64,
+    /// How often the planner may apply this mitigation in one plan.
+    #[serde(default = "one_use")]
+    pub max_uses: u32,
+}
+
+fn one() -> f64 {
+    1.0
+}
+
+fn one_use() -> u32 {
+    1
+}
+
+impl MitigationEffect {
+    pub fn apply(&self, snapshot: &mut EnvelopeSnapshot) {
+        snapshot.roh *= self.roh_factor;
+        snapshot.decay += self.decay_delta;
+        snapshot.lifeforce += self.lifeforce_delta;
+    }
+
+    fn is_valid(&self) -> bool {
+        self.roh_factor.is_finite()
+            && self.roh_factor >= 0.0
+            && self.decay_delta.is_finite()
+            && self.lifeforce_delta.is_finite()
+    }
+}
+
+/// Per-mitigation effects, keyed by [`Mitigation::name`]. Mitigations
+/// without an entry have no effect.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct EffectModel {
+    pub effects: BTreeMap<String, MitigationEffect>,
+}
+
+impl EffectModel {
+    /// RoH ×0.8, decay −0.1, lifeforce +0.15, one use each.
+    pub fn standard() -> Self {
+        let effect = |roh_factor, decay_delta, lifeforce_delta| MitigationEffect {
+            roh_factor,
+            decay_delta,
+            lifeforce_delta,
+            max_uses: 1,
+        };
+        let mut effects = BTreeMap::new();
+        effects.insert("TightenEnvelopes".to_string(), effect(0.8, 0.0, 0.0));
+        effects.insert("PauseOperations".to_string(), effect(1.0, -0.1, 0.0));
+        effects.insert("InduceRest".to_string(), effect(1.0, 0.0, 0.15));
+        Self { effects }
+    }
+
+    /// Standard model overlaid with the effects in a JSON config
+    /// (`{"effects": {"<name>": {"roh_factor": .., ..}}}`).
+    pub fn from_json(json: &str) -> Result<Self, crate::PlannerError> {
+        let config: EffectModel = serde_json::from_str(json)?;
+        let mut model = Self::standard();
+        for (name, effect) in config.effects {
+            if !effect.is_valid() {
+                return Err(crate::PlannerError::InvalidEffect(name));
+            }
+            model.effects.insert(name, effect);
+        }
+        Ok(model)
+    }
+
+    pub fn effect(&self, mitigation: &Mitigation) -> Option<&MitigationEffect> {
+        self.effects.get(mitigation.name())
+    }
+
+    pub fn apply(&self, mitigation: &Mitigation, snapshot: &mut EnvelopeSnapshot) {
+        if let Some(effect) = self.effect(mitigation) {
+            effect.apply(snapshot);
+        }
+    }
+}
@@ -0,0 +1,318 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use rand_distr::{Distribution, Normal};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{EnvelopeSnapshot, SAFE_ROH_CEILING};
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SimulationError {
+    #[error("{0} sigma must be finite and non-negative")]
+    InvalidSigma(&'static str),
+    #[error("{0} drift must be finite")]
+    InvalidDrift(&'static str),
+    #[error("RoH ceiling must not be NaN")]
+    InvalidCeiling,
+    #[error("snapshot RoH/decay/lifeforce must be finite")]
+    InvalidSnapshot,
+}
+
+/// Mean change per step for the stochastic dimensions.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct Drift {
+    pub roh: f64,
+    pub decay: f64,
+    pub lifeforce: f64,
+}
+
+/// Per-step standard deviations for the stochastic dimensions.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct NoiseModel {
+    pub roh_sigma: f64,
+    pub decay_sigma: f64,
+    pub lifeforce_sigma: f64,
+}
+
+impl NoiseModel {
+    /// Full-scale per-step sigma at `uncertainty == 1.0`.
+    pub const FULL_SCALE_SIGMA: f64 = 0.1;
+
+    /// Noise scaled by an evidence bundle's `uncertainty` (0..=1).
+    pub fn from_uncertainty(uncertainty: f64) -> Self {
+        let sigma = Self::FULL_SCALE_SIGMA * uncertainty.clamp(0.0, 1.0);
+        Self {
+            roh_sigma: sigma,
+            decay_sigma: sigma,
+            lifeforce_sigma: sigma,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MonteCarloConfig {
+    pub trajectories: usize,
+    pub steps: usize,
+    pub seed: u64,
+    pub drift: Drift,
+    pub noise: NoiseModel,
+    /// A trajectory breaches once RoH exceeds this; defaults to
+    /// [`SAFE_ROH_CEILING`].
+    pub roh_ceiling: f64,
+}
+
+impl MonteCarloConfig {
+    pub fn new(uncertainty: f64, steps: usize, seed: u64) -> Self {
+        Self {
+            trajectories: 10_000,
+            steps,
+            seed,
+            drift: Drift::default(),
+            noise: NoiseModel::from_uncertainty(uncertainty),
+            roh_ceiling: SAFE_ROH_CEILING,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), SimulationError> {
+        for (name, sigma) in [
+            ("roh", self.noise.roh_sigma),
+            ("decay", self.noise.decay_sigma),
+            ("lifeforce", self.noise.lifeforce_sigma),
+        ] {
+            if !(sigma.is_finite() && sigma >= 0.0) {
+                return Err(SimulationError::InvalidSigma(name));
+            }
+        }
+        for (name, drift) in [
+            ("roh", self.drift.roh),
+            ("decay", self.drift.decay),
+            ("lifeforce", self.drift.lifeforce),
+        ] {
+            if !drift.is_finite() {
+                return Err(SimulationError::InvalidDrift(name));
+            }
+        }
+        if self.roh_ceiling.is_nan() {
+            return Err(SimulationError::InvalidCeiling);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PercentileBand {
+    pub p05: f64,
+    pub p50: f64,
+    pub p95: f64,
+}
+
+impl PercentileBand {
+    fn from_samples(samples: &mut [f64]) -> Self {
+        samples.sort_by(f64::total_cmp);
+        if samples.is_empty() {
+            return Self {
+                p05: f64::NAN,
+                p50: f64::NAN,
+                p95: f64::NAN,
+            };
+        }
+        let at = |q: f64| {
+            let idx = ((samples.len() - 1) as f64 * q).round() as usize;
+            samples[idx]
+        };
+        Self {
+            p05: at(0.05),
+            p50: at(0.5),
+            p95: at(0.95),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StepBands {
+    pub step: usize,
+    pub roh: PercentileBand,
+    pub decay: PercentileBand,
+    pub lifeforce: PercentileBand,
+    /// Share of trajectories that have breached by this step.
+    pub cumulative_breach_probability: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MonteCarloReport {
+    pub trajectories: usize,
+    pub seed: u64,
+    pub roh_ceiling: f64,
+    /// Share of trajectories that breach at any step.
+    pub breach_probability: f64,
+    /// Step 0 is the starting snapshot.
+    pub bands: Vec<StepBands>,
+}
+
+impl MonteCarloReport {
+    /// One-sided 95% Wilson upper bound on the breach probability.
+    pub fn breach_probability_upper_95(&self) -> f64 {
+        let n = self.trajectories as f64;
+        if n == 0.0 {
+            return 1.0;
+        }
+        let z = 1.645_f64;
+        let p = self.breach_probability;
+        let denom = 1.0 + z * z / n;
+        let centre = p + z * z / (2.0 * n);
+        let spread = z * (p * (1.0 - p) / n + z * z / (4.0 * n * n)).sqrt();
+        ((centre + spread) / denom).min(1.0)
+    }
+
+    /// Review-board check, e.g. `meets(0.01)` for "P(breach) < 1%".
+    pub fn meets(&self, max_breach_probability: f64) -> bool {
+        self.breach_probability_upper_95() < max_breach_probability
+    }
+}
+
+fn normal(name: &'static str, sigma: f64) -> Result<Normal<f64>, SimulationError> {
+    Normal::new(0.0, sigma).map_err(|_| SimulationError::InvalidSigma(name))
+}
+
+/// Run seeded trajectories from `start`. RoH and decay are floored at 0 and
+/// lifeforce is kept within 0..=1; other envelope dimensions do not evolve.
+pub fn simulate(
+    start: &EnvelopeSnapshot,
+    config: &MonteCarloConfig,
+) -> Result<MonteCarloReport, SimulationError> {
+    config.validate()?;
+    if ![start.roh, start.decay, start.lifeforce]
+        .iter()
+        .all(|v| v.is_finite())
+    {
+        return Err(SimulationError::InvalidSnapshot);
+    }
+    let mut rng = ChaCha8Rng::seed_from_u64(config.seed);
+    let (roh_noise, decay_noise, life_noise) = (
+        normal("roh", config.noise.roh_sigma)?,
+        normal("decay", config.noise.decay_sigma)?,
+        normal("lifeforce", config.noise.lifeforce_sigma)?,
+    );
+
+    let n = config.trajectories;
+    let mut roh = vec![vec![0.0; n]; config.steps + 1];
+    let mut decay = roh.clone();
+    let mut life = roh.clone();
+    let mut breached_at = vec![None; n];
+
+    for t in 0..n {
+        let mut state = start.clone();
+        for step in 0..=config.steps {
+            if step > 0 {
+                state.roh = (state.roh + config.drift.roh + roh_noise.sample(&mut rng)).max(0.0);
+                state.decay =
+                    (state.decay + config.drift.decay + decay_noise.sample(&mut rng)).max(0.0);
+                state.lifeforce =
+                    (state.lifeforce + config.drift.lifeforce + life_noise.sample(&mut rng))
+                        .clamp(0.0, 1.0);
+            }
+            if breached_at[t].is_none() && state.roh > config.roh_ceiling {
+                breached_at[t] = Some(step);
+            }
+            roh[step][t] = state.roh;
+            decay[step][t] = state.decay;
+            life[step][t] = state.lifeforce;
+        }
+    }
+
+    let bands = (0..=config.steps)
+        .map(|step| StepBands {
+            step,
+            roh: PercentileBand::from_samples(&mut roh[step]),
+            decay: PercentileBand::from_samples(&mut decay[step]),
+            lifeforce: PercentileBand::from_samples(&mut life[step]),
+            cumulative_breach_probability: breached_at
+                .iter()
+                .filter(|b| b.is_some_and(|s| s <= step))
+                .count() as f64
+                / n.max(1) as f64,
+        })
+        .collect::<Vec<_>>();
+
+    Ok(MonteCarloReport {
+        trajectories: n,
+        seed: config.seed,
+        roh_ceiling: config.roh_ceiling,
+        breach_probability: bands
+            .last()
+            .map_or(0.0, |b| b.cumulative_breach_probability),
+        bands,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start(roh: f64) -> EnvelopeSnapshot {
+        EnvelopeSnapshot {
+            roh,
+            decay: 0.5,
+            lifeforce: 0.5,
+            power: 0.9,
+            tech: 0.9,
+            nano: 0.8,
+            smart: 0.9,
+        }
+    }
+
+    #[test]
+    fn seeded_runs_are_reproducible() {
+        let mut config = MonteCarloConfig::new(0.3, 20, 7);
+        config.trajectories = 500;
+        let a = simulate(&start(0.2), &config).unwrap();
+        let b = simulate(&start(0.2), &config).unwrap();
+        assert_eq!(a.breach_probability, b.breach_probability);
+        assert_eq!(a.bands[20].roh, b.bands[20].roh);
+        assert!(a.bands[20].roh.p05 <= a.bands[20].roh.p50);
+        assert!(a.bands[20].roh.p50 <= a.bands[20].roh.p95);
+    }
+
+    #[test]
+    fn certain_evidence_has_no_spread() {
+        let mut config = MonteCarloConfig::new(0.0, 10, 1);
+        config.trajectories = 200;
+        let report = simulate(&start(0.1), &config).unwrap();
+        assert_eq!(report.breach_probability, 0.0);
+        let last = &report.bands[10].roh;
+        assert_eq!((last.p05, last.p95), (0.1, 0.1));
+        assert!(report.meets(0.02));
+        assert!(!report.meets(0.01));
+    }
+
+    #[test]
+    fn upward_drift_breaches() {
+        let mut config = MonteCarloConfig::new(0.1, 10, 3);
+        assert_eq!(config.roh_ceiling, SAFE_ROH_CEILING);
+        config.trajectories = 200;
+        config.drift.roh = 0.05;
+        let report = simulate(&start(0.1), &config).unwrap();
+        assert_eq!(report.breach_probability, 1.0);
+        assert_eq!(report.bands[0].cumulative_breach_probability, 0.0);
+        assert!(!report.meets(0.01));
+    }
+
+    #[test]
+    fn rejects_non_finite_noise() {
+        let mut config = MonteCarloConfig::new(0.1, 10, 3);
+        config.trajectories = 10;
+        config.noise.decay_sigma = f64::INFINITY;
+        assert_eq!(
+            simulate(&start(0.1), &config).unwrap_err(),
+            SimulationError::InvalidSigma("decay")
+        );
+        config.noise.decay_sigma = 0.1;
+        config.noise.roh_sigma = f64::NAN;
+        assert!(simulate(&start(0.1), &config).is_err());
+        config.noise.roh_sigma = 0.1;
+        assert_eq!(
+            simulate(&start(f64::NAN), &config).unwrap_err(),
+            SimulationError::InvalidSnapshot
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use reversal_policy::{ReversalPolicy, SaferAlternativeEvidence};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{reproject_with_model, EffectModel, EnvelopeSnapshot, Mitigation, SAFE_ROH_CEILING};
+
+#[derive(Debug, Error)]
+pub enum PlannerError {
+    #[error("invalid effect config: {0}")]
+    Config(#[from] serde_json::Error),
+    #[error("mitigation `{0}` has a non-finite or negative effect")]
+    InvalidEffect(String),
+    #[error("snapshot RoH/decay must be finite")]
+    InvalidSnapshot,
+    #[error("search space of {0} mitigation sets exceeds the planner limit")]
+    SearchTooLarge(u128),
+}
+
+/// Shortest mitigation sequence whose projection [`reproject_with_model`]
+/// judges safe.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MitigationPlan {
+    pub sequence: Vec<Mitigation>,
+    pub projected: EnvelopeSnapshot,
+}
+
+/// Every mitigation set within the use limits was tried and none was safe.
+/// `best_roh`/`best_decay` come from the same set, the one with the lowest RoH.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NoSaferAlternativeProof {
+    pub explored: u128,
+    pub roh_ceiling: f64,
+    pub best_roh: f64,
+    pub best_decay: f64,
+}
+
+impl NoSaferAlternativeProof {
+    /// Evidence for the reversal gate, using the most favourable projection.
+    pub fn evidence(&self) -> SaferAlternativeEvidence {
+        SaferAlternativeEvidence {
+            mitigations_exhausted: true,
+            roh: self.best_roh,
+            decay: self.best_decay,
+        }
+    }
+
+    /// Whether the evidence also meets the policy's `nosaferalternative`.
+    pub fn establishes_nosaferalternative(&self) -> bool {
+        ReversalPolicy::standard().no_safer_alternative(&self.evidence())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum PlanOutcome {
+    Safe(MitigationPlan),
+    NoSaferAlternative(NoSaferAlternativeProof),
+}
+
+/// Searches mitigation sets in order of size. Effects commute (RoH scales,
+/// decay and lifeforce shift), so sets stand in for sequences and the first
+/// safe set found is minimal.
+#[derive(Clone, Debug)]
+pub struct MitigationPlanner {
+    pub model: EffectModel,
+    pub candidates: Vec<Mitigation>,
+    pub max_search: u128,
+}
+
+impl MitigationPlanner {
+    /// Plan over every mitigation the model defines.
+    pub fn new(model: EffectModel) -> Self {
+        let candidates = model
+            .effects
+            .keys()
+            .map(|name| match name.as_str() {
+                "TightenEnvelopes" => Mitigation::TightenEnvelopes,
+                "PauseOperations" => Mitigation::PauseOperations,
+                "InduceRest" => Mitigation::InduceRest,
+                other => Mitigation::Custom(other.to_string()),
+            })
+            .collect();
+        Self {
+            model,
+            candidates,
+            max_search: 1_000_000,
+        }
+    }
+
+    fn uses(&self, mitigation: &Mitigation) -> u32 {
+        self.model.effect(mitigation).map_or(0, |e| e.max_uses)
+    }
+
+    fn sequence(&self, counts: &[u32]) -> Vec<Mitigation> {
+        self.candidates
+            .iter()
+            .zip(counts)
+            .flat_map(|(m, &n)| std::iter::repeat_n(m.clone(), n as usize))
+            .collect()
+    }
+
+    pub fn plan(&self, snapshot: &EnvelopeSnapshot) -> Result<PlanOutcome, PlannerError> {
+        if !snapshot.roh.is_finite() || !snapshot.decay.is_finite() {
+            return Err(PlannerError::InvalidSnapshot);
+        }
+        let limits: Vec<u32> = self.candidates.iter().map(|m| self.uses(m)).collect();
+        let space = limits
+            .iter()
+            .try_fold(1u128, |acc, &n| acc.checked_mul(n as u128 + 1))
+            .unwrap_or(u128::MAX);
+        if space > self.max_search {
+            return Err(PlannerError::SearchTooLarge(space));
+        }
+
+        let total: u32 = limits.iter().sum();
+        let mut best: Option<EnvelopeSnapshot> = None;
+        let mut explored = 0u128;
+        for size in 0..=total {
+            let mut found = None;
+            for_each_set(&limits, size, &mut Vec::new(), &mut |counts| {
+                if found.is_some() {
+                    return;
+                }
+                explored += 1;
+                let sequence = self.sequence(counts);
+                let (projected, safe) = reproject_with_model(snapshot, &sequence, &self.model);
+                if safe {
+                    found = Some(MitigationPlan {
+                        sequence,
+                        projected,
+                    });
+                } else if best.as_ref().is_none_or(|b| projected.roh < b.roh) {
+                    best = Some(projected);
+                }
+            });
+            if let Some(plan) = found {
+                return Ok(PlanOutcome::Safe(plan));
+            }
+        }
+        let best = best.unwrap_or_else(|| snapshot.clone());
+        Ok(PlanOutcome::NoSaferAlternative(NoSaferAlternativeProof {
+            explored,
+            roh_ceiling: SAFE_ROH_CEILING,
+            best_roh: best.roh,
+            best_decay: best.decay,
+        }))
+    }
+}
+
+/// Visit every count vector with `counts[i] <= limits[i]` summing to `left`,
+/// favouring earlier candidates.
+fn for_each_set(limits: &[u32], left: u32, counts: &mut Vec<u32>, visit: &mut dyn FnMut(&[u32])) {
+    let i = counts.len();
+    if i == limits.len() {
+        if left == 0 {
+            visit(counts);
+        }
+        return;
+    }
+    let rest: u32 = limits[i + 1..].iter().sum();
+    for n in (0..=limits[i].min(left)).rev() {
+        if left - n > rest {
+            break;
+        }
+        counts.push(n);
+        for_each_set(limits, left - n, counts, visit);
+        counts.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(roh: f64, decay: f64) -> EnvelopeSnapshot {
+        EnvelopeSnapshot {
+            roh,
+            decay,
+            lifeforce: 0.5,
+            power: 0.5,
+            tech: 0.5,
+            nano: 0.8,
+            smart: 0.9,
+        }
+    }
+
+    #[test]
+    fn finds_minimal_sequence() {
+        let planner = MitigationPlanner::new(EffectModel::standard());
+        match planner.plan(&snapshot(0.35, 0.9)).unwrap() {
+            PlanOutcome::Safe(plan) => {
+                assert_eq!(plan.sequence, vec![Mitigation::TightenEnvelopes]);
+                assert!(plan.projected.roh <= 0.3);
+            }
+            other => panic!("expected a plan, got {other:?}"),
+        }
+        match planner.plan(&snapshot(0.2, 0.9)).unwrap() {
+            PlanOutcome::Safe(plan) => assert!(plan.sequence.is_empty()),
+            other => panic!("expected a plan, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn custom_effects_from_config() {
+        let model = EffectModel::from_json(
+            r#"{"effects": {"TightenEnvelopes": {"roh_factor": 0.9, "max_uses": 2},
+                            "Cooling": {"roh_factor": 0.7, "decay_delta": -0.05}}}"#,
+        )
+        .unwrap();
+        let planner = MitigationPlanner::new(model);
+        match planner.plan(&snapshot(0.5, 0.9)).unwrap() {
+            PlanOutcome::Safe(plan) => {
+                assert_eq!(
+                    plan.sequence,
+                    vec![
+                        Mitigation::Custom("Cooling".to_string()),
+                        Mitigation::TightenEnvelopes,
+                        Mitigation::TightenEnvelopes,
+                    ]
+                );
+            }
+            other => panic!("expected a plan, got {other:?}"),
+        }
+        assert!(matches!(
+            EffectModel::from_json(r#"{"effects": {"Bad": {"roh_factor": -1.0}}}"#),
+            Err(PlannerError::InvalidEffect(_))
+        ));
+    }
+
+    #[test]
+    fn exhausted_search_proves_no_safer_alternative() {
+        let planner = MitigationPlanner::new(EffectModel::standard());
+        match planner.plan(&snapshot(0.9, 1.2)).unwrap() {
+            PlanOutcome::NoSaferAlternative(proof) => {
+                assert_eq!(proof.explored, 8);
+                assert!((proof.best_roh - 0.72).abs() < 1e-9);
+                // Decay of the same set, not PauseOperations' lower decay.
+                assert_eq!(proof.best_decay, 1.2);
+                assert!(proof.establishes_nosaferalternative());
+            }
+            other => panic!("expected a proof, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn agrees_with_projector_outside_nano_envelope() {
+        let planner = MitigationPlanner::new(EffectModel::standard());
+        let mut outside = snapshot(0.2, 0.9);
+        outside.nano = 0.5;
+        assert!(!reproject_with_model(&outside, &[], &planner.model).1);
+        assert!(matches!(
+            planner.plan(&outside).unwrap(),
+            PlanOutcome::NoSaferAlternative(_)
+        ));
+    }
+}
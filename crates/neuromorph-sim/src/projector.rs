@@ -1,24 +1,26 @@
-use crate::{EnvelopeSnapshot, Mitigation};
+use reversal_policy::{ReversalPolicy, SaferAlternativeEvidence};
+
+use crate::{EffectModel, EnvelopeSnapshot, Mitigation, SAFE_ROH_CEILING};
 
 pub fn reproject_non_reversal(
     snapshot: &EnvelopeSnapshot,
     mitigations: &[Mitigation],
+) -> (EnvelopeSnapshot, bool) {
+    reproject_with_model(snapshot, mitigations, &EffectModel::standard())
+}
+
+/// As [`reproject_non_reversal`], with mitigation effects taken from `model`.
+/// The projection is safe when the nano/smart envelope holds and RoH is
+/// within [`SAFE_ROH_CEILING`].
+pub fn reproject_with_model(
+    snapshot: &EnvelopeSnapshot,
+    mitigations: &[Mitigation],
+    model: &EffectModel,
 ) -> (EnvelopeSnapshot, bool) {
     let mut projected = snapshot.clone();
 
     for mitigation in mitigations {
-        match mitigation {
-            Mitigation::TightenEnvelopes => {
-                projected.roh *= 0.8;
-            }
-            Mitigation::PauseOperations => {
-                projected.decay -= 0.1;
-            }
-            Mitigation::InduceRest => {
-                projected.lifeforce += 0.15;
-            }
-            Mitigation::Custom(_) => {}
-        }
+        model.apply(mitigation, &mut projected);
     }
 
     if projected.nano > 0.7 && projected.smart > 0.8 {
@@ -28,13 +30,20 @@ pub fn reproject_non_reversal(
         if projected.tech < 0.9 {
             projected.tech = 0.9;
         }
-        let safe = projected.roh <= 0.3 && !nosaferalternative(&projected);
+        let safe = projected.roh <= SAFE_ROH_CEILING;
         (projected, safe)
     } else {
         (projected, false)
     }
 }
 
-pub fn nosaferalternative(snapshot: &EnvelopeSnapshot) -> bool {
-    snapshot.roh > 0.3 && snapshot.decay > 0.8
+/// `nosaferalternative` for a post-mitigation snapshot. `mitigations_exhausted`
+/// is evidence the caller must hold, e.g. a [`crate::NoSaferAlternativeProof`]
+/// from the planner; a single projection does not establish it.
+pub fn nosaferalternative(snapshot: &EnvelopeSnapshot, mitigations_exhausted: bool) -> bool {
+    ReversalPolicy::standard().no_safer_alternative(&SaferAlternativeEvidence {
+        mitigations_exhausted,
+        roh: snapshot.roh,
+        decay: snapshot.decay,
+    })
 }
@@ -2,7 +2,8 @@ mod model;
 mod scheduler;
 
 use anyhow::Result;
-use ceim-kernel::TimeSample;
+use ceim_kernel::TimeSample;
+use chrono::Utc;
 
 use model::{Basin, ScheduleOption};
 use scheduler::rank_schedules;
@@ -55,7 +56,7 @@ fn main() -> Result<()> {
         },
     ];
 
-    let ranked = rank_schedules(&basins, &options, &samples)?;
+    let ranked = rank_schedules(&basins, &options, &samples, Utc::now().date_naive())?;
     for r in ranked {
         println!(
             "{} {}-{} K_n/kWh={:.3} K_n/ha={:.3}",
@@ -1,27 +1,22 @@
 use anyhow::Result;
+use chrono::NaiveDate;
 
-use ceim-kernel::{CeimKernel, RegulatoryLimits, TimeSample};
+use ceim_kernel::{CeimKernel, LimitsCatalog, TimeSample};
 
 use crate::model::{Basin, RankedSchedule, ScheduleOption};
 
+/// Rank schedules against the nitrate limits in force on `on`.
 pub fn rank_schedules(
     basins: &[Basin],
     options: &[ScheduleOption],
     samples: &[TimeSample],
+    on: NaiveDate,
 ) -> Result<Vec<RankedSchedule>> {
+    let limits = LimitsCatalog::standard().limits_for("nitrate", on);
     let mut results = Vec::new();
     for opt in options {
         if let Some(basin) = basins.iter().find(|b| b.id == opt.basin_id) {
-            let impact = CeimKernel::compute(
-                "nitrate",
-                1.0,
-                samples,
-                &RegulatoryLimits {
-                    epa: Some(1.0),
-                    eu: Some(1.0),
-                    who: Some(1.0),
-                },
-            );
+            let impact = CeimKernel::compute("nitrate", 1.0, samples, &limits)?;
             let k_n_per_kwh = impact.k_n / basin.energy_kwh_per_day.max(1.0);
             let k_n_per_hectare = impact.k_n / basin.area_ha.max(0.1);
             results.push(RankedSchedule {
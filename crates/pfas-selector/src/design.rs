@@ -1,3 +1,4 @@
+use ceim_kernel::{Concentration, ConcentrationUnit};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -11,6 +12,13 @@ pub enum TrainType {
 pub struct TreatmentDesign {
     pub name: String,
     pub train_type: TrainType,
+    /// Effluent concentration in ng/L.
     pub c_out: f64,
     pub energy_kwh: f64,
 }
+
+impl TreatmentDesign {
+    pub fn c_out(&self) -> Concentration {
+        Concentration::new(self.c_out, ConcentrationUnit::NgPerL)
+    }
+}
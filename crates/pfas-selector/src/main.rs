@@ -2,33 +2,31 @@ mod design;
 mod supreme;
 
 use anyhow::Result;
-use ceim-kernel::{RegulatoryLimits, TimeSample};
+use ceim_kernel::{Concentration, ConcentrationUnit, LimitsCatalog, TimeSample};
+use chrono::Utc;
 
 use design::{TrainType, TreatmentDesign};
 use supreme::select_designs;
 
 fn main() -> Result<()> {
-    let c_in = 3.9;
+    let contaminant = "pfoa";
+    let c_in = Concentration::new(3.9, ConcentrationUnit::NgPerL);
     let flows = vec![
         TimeSample {
             t_hours: 0.0,
-            c_in,
+            c_in: c_in.as_mg_per_l(),
             c_out: 0.0,
             flow_q: 1.0,
         },
         TimeSample {
             t_hours: 24.0,
-            c_in,
+            c_in: c_in.as_mg_per_l(),
             c_out: 0.0,
             flow_q: 1.0,
         },
     ];
 
-    let limits = RegulatoryLimits {
-        epa: Some(4.0),
-        eu: Some(2.0),
-        who: Some(2.0),
-    };
+    let limits = LimitsCatalog::standard().limits_for(contaminant, Utc::now().date_naive());
 
     let designs = vec![
         TreatmentDesign {
@@ -51,7 +49,7 @@ fn main() -> Result<()> {
         },
     ];
 
-    let selected = select_designs(c_in, &flows, &limits, designs)?;
+    let selected = select_designs(contaminant, c_in, &flows, &limits, designs)?;
     for s in selected {
         println!(
             "{} {:?} K_n={:.3} K_n/kWh={:.3}",
@@ -1,5 +1,8 @@
-use anyhow::Result;
-use ceim-kernel::{CeimKernel, RegulatoryLimits, TimeSample};
+use anyhow::{anyhow, Result};
+use ceim_kernel::{
+    CeimKernel, Concentration, FlowRate, Hours, MassLoadOptions, MassLoadSample, RegulatoryLimits,
+    TimeSample,
+};
 
 use crate::design::TreatmentDesign;
 
@@ -9,31 +12,44 @@ pub struct DesignScore {
     pub k_n_per_kwh: f64,
 }
 
+/// Rank designs whose effluent meets the supreme limit by K_n per kWh.
+/// `flows` supplies times (h) and flows (m³/h); concentrations come from
+/// `c_in` and each design.
 pub fn select_designs(
-    c_in: f64,
+    contaminant: &str,
+    c_in: Concentration,
     flows: &[TimeSample],
     limits: &RegulatoryLimits,
     designs: Vec<TreatmentDesign>,
 ) -> Result<Vec<DesignScore>> {
-    let supreme = limits.supreme();
+    let supreme = limits
+        .supreme()
+        .ok_or_else(|| anyhow!("{contaminant} is unregulated under the given limits"))?;
     let mut out = Vec::new();
 
     for d in designs {
-        if d.c_out > supreme.value {
+        let c_out = d.c_out();
+        if c_out.as_mg_per_l() > supreme.value {
             continue;
         }
 
-        let samples: Vec<TimeSample> = flows
+        let samples: Vec<MassLoadSample> = flows
             .iter()
-            .map(|s| TimeSample {
-                t_hours: s.t_hours,
+            .map(|s| MassLoadSample {
+                t: Hours(s.t_hours),
                 c_in,
-                c_out: d.c_out,
-                flow_q: s.flow_q,
+                c_out,
+                flow: FlowRate::m3_per_h(s.flow_q),
             })
             .collect();
 
-        let impact = CeimKernel::compute("PFAS", 1.0, &samples, limits);
+        let impact = CeimKernel::compute_typed(
+            contaminant,
+            1.0,
+            &samples,
+            &MassLoadOptions::default(),
+            limits,
+        )?;
         let score = DesignScore {
             k_n_per_kwh: impact.k_n / d.energy_kwh.max(1.0),
             k_n: impact.k_n,
@@ -42,6 +58,6 @@ pub fn select_designs(
         out.push(score);
     }
 
-    out.sort_by(|a, b| b.k_n_per_kwh.partial_cmp(&a.k_n_per_kwh).unwrap());
+    out.sort_by(|a, b| b.k_n_per_kwh.total_cmp(&a.k_n_per_kwh));
     Ok(out)
 }
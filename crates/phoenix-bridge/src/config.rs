@@ -1,8 +1,71 @@
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use ceim_kernel::AggregationConfig;
 use serde::Deserialize;
+use shard_store::RetentionPolicy;
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct Config {
     pub poll_interval_seconds: u64,
-    pub water_quality_feed_url: String,
+    /// Shorthand for an `http` source when `source` is not set.
+    #[serde(default)]
+    pub water_quality_feed_url: Option<String>,
+    #[serde(default)]
+    pub source: Option<SourceConfig>,
     pub output_dir: String,
+    /// How far back samples are kept across ticks when computing K_n.
+    #[serde(default = "default_lookback_hours")]
+    pub lookback_hours: i64,
+    /// Omega weights and mixture terms for the node-level ecoimpact score.
+    #[serde(default)]
+    pub aggregation: AggregationConfig,
+    /// Applied to `output_dir` after every shard is written.
+    #[serde(default)]
+    pub retention: RetentionPolicy,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SourceConfig {
+    Http {
+        url: String,
+    },
+    File {
+        path: PathBuf,
+    },
+    Directory {
+        path: PathBuf,
+    },
+    Stdin {
+        #[serde(default = "default_max_batch")]
+        max_batch: usize,
+    },
+    /// Replays a recorded file; each poll interval of feed time takes
+    /// `1/speed` of the interval in real time. No `speed` means no pauses.
+    Replay {
+        path: PathBuf,
+        #[serde(default)]
+        speed: Option<f64>,
+    },
+}
+
+impl Config {
+    pub fn source(&self) -> Result<SourceConfig> {
+        match (&self.source, &self.water_quality_feed_url) {
+            (Some(source), _) => Ok(source.clone()),
+            (None, Some(url)) => Ok(SourceConfig::Http { url: url.clone() }),
+            (None, None) => Err(anyhow!(
+                "config needs either `source` or `water_quality_feed_url`"
+            )),
+        }
+    }
+}
+
+fn default_lookback_hours() -> i64 {
+    24
+}
+
+fn default_max_batch() -> usize {
+    1000
 }
@@ -1,6 +1,12 @@
-use anyhow::Result;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use anyhow::{anyhow, bail, Context, Result};
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader, Lines, Stdin};
+use tracing::warn;
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct WaterSample {
@@ -12,8 +18,407 @@ pub struct WaterSample {
     pub flow_q: f64,
 }
 
-pub async fn fetch_samples(feed_url: &str) -> Result<Vec<WaterSample>> {
-    let resp = reqwest::get(feed_url).await?;
-    let samples: Vec<WaterSample> = resp.json().await?;
-    Ok(samples)
+/// Where samples come from. Each call to `next_batch` is one poll;
+/// `Ok(None)` means the source is exhausted and the bridge should stop.
+pub trait SampleSource {
+    async fn next_batch(&mut self) -> Result<Option<Vec<WaterSample>>>;
+
+    /// Time the last batch represents; replayed feeds report feed time.
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    /// How long to wait before the next poll, given the configured interval.
+    fn pause(&self, poll_interval: Duration) -> Duration {
+        poll_interval
+    }
+}
+
+pub fn parse_ndjson(raw: &str) -> Result<Vec<WaterSample>> {
+    raw.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line).with_context(|| format!("NDJSON line {}", i + 1))
+        })
+        .collect()
+}
+
+/// CSV with a header naming the `WaterSample` fields, in any order.
+pub fn parse_csv(raw: &str) -> Result<Vec<WaterSample>> {
+    let mut lines = raw
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty());
+    let Some((_, header)) = lines.next() else {
+        return Ok(Vec::new());
+    };
+    let columns: Vec<&str> = header.split(',').map(str::trim).collect();
+    let col = |name: &str| {
+        columns
+            .iter()
+            .position(|c| *c == name)
+            .ok_or_else(|| anyhow!("CSV header is missing `{name}`"))
+    };
+    let idx = [
+        col("timestamp")?,
+        col("node_id")?,
+        col("contaminant")?,
+        col("c_in")?,
+        col("c_out")?,
+        col("flow_q")?,
+    ];
+    lines
+        .map(|(i, line)| {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let field = |k: usize| {
+                fields
+                    .get(idx[k])
+                    .copied()
+                    .ok_or_else(|| anyhow!("CSV line {}: too few fields", i + 1))
+            };
+            let number = |k: usize| -> Result<f64> {
+                field(k)?
+                    .parse()
+                    .with_context(|| format!("CSV line {}", i + 1))
+            };
+            Ok(WaterSample {
+                timestamp: field(0)?
+                    .parse()
+                    .with_context(|| format!("CSV line {}", i + 1))?,
+                node_id: field(1)?.to_string(),
+                contaminant: field(2)?.to_string(),
+                c_in: number(3)?,
+                c_out: number(4)?,
+                flow_q: number(5)?,
+            })
+        })
+        .collect()
+}
+
+/// Parse by extension: `.csv` as CSV, `.json` as a JSON array, anything
+/// else as NDJSON.
+pub fn read_samples(path: &Path) -> Result<Vec<WaterSample>> {
+    let raw = std::fs::read_to_string(path).with_context(|| format!("{}", path.display()))?;
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("csv") => parse_csv(&raw),
+        Some("json") => Ok(serde_json::from_str(&raw)?),
+        _ => parse_ndjson(&raw),
+    }
+}
+
+pub struct HttpSource {
+    url: String,
+}
+
+impl HttpSource {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into() }
+    }
+}
+
+impl SampleSource for HttpSource {
+    async fn next_batch(&mut self) -> Result<Option<Vec<WaterSample>>> {
+        let resp = reqwest::get(&self.url).await?;
+        Ok(Some(resp.json().await?))
+    }
+}
+
+/// A single NDJSON, CSV or JSON file, read once.
+pub struct FileSource {
+    path: Option<PathBuf>,
+}
+
+impl FileSource {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: Some(path.into()),
+        }
+    }
+}
+
+impl SampleSource for FileSource {
+    async fn next_batch(&mut self) -> Result<Option<Vec<WaterSample>>> {
+        self.path.take().map(|p| read_samples(&p)).transpose()
+    }
+}
+
+/// Size and modification time of a polled file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct FileStamp {
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FileState {
+    /// Seen with this stamp; read once it is unchanged at the next poll.
+    Pending(FileStamp),
+    Read,
+    /// Failed with this stamp; retried once the file changes.
+    Failed(FileStamp),
+}
+
+/// Polls a directory and reads each new file once, in file-name order. A
+/// file is read only after its size and mtime are unchanged across two
+/// polls, so files still being written are not consumed early. A file that
+/// cannot be read or parsed is logged and skipped until it changes; it does
+/// not hold back the rest of the batch. Only files still in the directory
+/// are tracked.
+pub struct DirectorySource {
+    dir: PathBuf,
+    files: HashMap<PathBuf, FileState>,
+}
+
+impl DirectorySource {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            files: HashMap::new(),
+        }
+    }
+}
+
+impl SampleSource for DirectorySource {
+    async fn next_batch(&mut self) -> Result<Option<Vec<WaterSample>>> {
+        let mut listed = HashMap::new();
+        for entry in std::fs::read_dir(&self.dir)?.filter_map(|e| e.ok()) {
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            if meta.is_file() {
+                let stamp = FileStamp {
+                    len: meta.len(),
+                    modified: meta.modified().ok(),
+                };
+                listed.insert(entry.path(), stamp);
+            }
+        }
+        self.files.retain(|path, _| listed.contains_key(path));
+
+        let mut ready = Vec::new();
+        for (path, stamp) in listed {
+            match self.files.get(&path) {
+                Some(FileState::Read) => {}
+                Some(FileState::Pending(prev)) if *prev == stamp => ready.push((path, stamp)),
+                Some(FileState::Failed(prev)) if *prev == stamp => {}
+                _ => {
+                    self.files.insert(path, FileState::Pending(stamp));
+                }
+            }
+        }
+        ready.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut batch = Vec::new();
+        for (path, stamp) in ready {
+            let state = match read_samples(&path) {
+                Ok(samples) => {
+                    batch.extend(samples);
+                    FileState::Read
+                }
+                Err(e) => {
+                    warn!("skipping {}: {e:#}", path.display());
+                    FileState::Failed(stamp)
+                }
+            };
+            self.files.insert(path, state);
+        }
+        Ok(Some(batch))
+    }
+}
+
+/// NDJSON on stdin, up to `max_batch` lines per poll. Malformed lines are
+/// logged and skipped.
+pub struct StdinSource<R = Stdin> {
+    lines: Lines<BufReader<R>>,
+    max_batch: usize,
+    line_no: usize,
+}
+
+impl StdinSource {
+    pub fn new(max_batch: usize) -> Self {
+        Self::from_reader(tokio::io::stdin(), max_batch)
+    }
+}
+
+impl<R: AsyncRead + Unpin> StdinSource<R> {
+    pub fn from_reader(reader: R, max_batch: usize) -> Self {
+        Self {
+            lines: BufReader::new(reader).lines(),
+            max_batch: max_batch.max(1),
+            line_no: 0,
+        }
+    }
+}
+
+impl<R: AsyncRead + Unpin> SampleSource for StdinSource<R> {
+    async fn next_batch(&mut self) -> Result<Option<Vec<WaterSample>>> {
+        let mut batch = Vec::new();
+        while batch.len() < self.max_batch {
+            let line = self.lines.next_line().await?;
+            self.line_no += 1;
+            match line {
+                Some(line) if line.trim().is_empty() => continue,
+                Some(line) => match serde_json::from_str(&line) {
+                    Ok(sample) => batch.push(sample),
+                    Err(e) => warn!("skipping stdin line {}: {e}", self.line_no),
+                },
+                None if batch.is_empty() => return Ok(None),
+                None => break,
+            }
+        }
+        Ok(Some(batch))
+    }
+
+    fn pause(&self, _poll_interval: Duration) -> Duration {
+        Duration::ZERO
+    }
+}
+
+/// Plays a recorded feed back in timestamp order. Each batch advances a feed
+/// clock by `step` and releases every sample up to it; `speed` divides the
+/// real-time pause between batches, and a non-finite speed does not pause.
+pub struct ReplaySource {
+    pending: VecDeque<WaterSample>,
+    step: chrono::Duration,
+    speed: f64,
+    clock: DateTime<Utc>,
+    next_clock: Option<DateTime<Utc>>,
+}
+
+impl ReplaySource {
+    pub fn new(mut samples: Vec<WaterSample>, step: chrono::Duration, speed: f64) -> Result<Self> {
+        if step <= chrono::Duration::zero() {
+            bail!("replay step must be positive");
+        }
+        if speed.is_nan() || speed <= 0.0 {
+            bail!("replay speed must be positive");
+        }
+        samples.sort_by_key(|s| s.timestamp);
+        Ok(Self {
+            next_clock: samples.first().map(|s| s.timestamp),
+            pending: samples.into(),
+            step,
+            speed,
+            clock: DateTime::<Utc>::UNIX_EPOCH,
+        })
+    }
+}
+
+impl SampleSource for ReplaySource {
+    async fn next_batch(&mut self) -> Result<Option<Vec<WaterSample>>> {
+        let Some(clock) = self.next_clock.filter(|_| !self.pending.is_empty()) else {
+            return Ok(None);
+        };
+        let mut batch = Vec::new();
+        while self.pending.front().is_some_and(|s| s.timestamp <= clock) {
+            batch.extend(self.pending.pop_front());
+        }
+        self.clock = clock;
+        self.next_clock = Some(clock + self.step);
+        Ok(Some(batch))
+    }
+
+    fn now(&self) -> DateTime<Utc> {
+        self.clock
+    }
+
+    fn pause(&self, _poll_interval: Duration) -> Duration {
+        if self.speed.is_finite() {
+            self.step
+                .to_std()
+                .map_or(Duration::ZERO, |d| d.div_f64(self.speed))
+        } else {
+            Duration::ZERO
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "node_id,timestamp,contaminant,c_in,c_out,flow_q
+n1,2025-01-01T00:00:00Z,nitrate,10,2,1
+n1,2025-01-01T02:00:00Z,nitrate,8,2,1
+n1,2025-01-01T01:00:00Z,nitrate,9,2,1
+";
+
+    #[tokio::test]
+    async fn replay_releases_samples_by_feed_time() {
+        let samples = parse_csv(CSV).unwrap();
+        assert_eq!(samples.len(), 3);
+        let mut replay =
+            ReplaySource::new(samples, chrono::Duration::minutes(90), f64::INFINITY).unwrap();
+        let first = replay.next_batch().await.unwrap().unwrap();
+        assert_eq!(first.len(), 1);
+        let second = replay.next_batch().await.unwrap().unwrap();
+        assert_eq!(second.len(), 1);
+        assert_eq!(replay.now().to_rfc3339(), "2025-01-01T01:30:00+00:00");
+        assert_eq!(replay.next_batch().await.unwrap().unwrap().len(), 1);
+        assert!(replay.next_batch().await.unwrap().is_none());
+        assert_eq!(replay.pause(Duration::from_secs(60)), Duration::ZERO);
+    }
+
+    #[test]
+    fn csv_and_ndjson_agree() {
+        let ndjson = r#"{"timestamp":"2025-01-01T00:00:00Z","node_id":"n1","contaminant":"nitrate","c_in":10,"c_out":2,"flow_q":1}"#;
+        let a = &parse_ndjson(ndjson).unwrap()[0];
+        let b = &parse_csv(CSV).unwrap()[0];
+        assert_eq!(
+            (a.timestamp, a.c_in, &a.node_id),
+            (b.timestamp, b.c_in, &b.node_id)
+        );
+        assert!(parse_csv("timestamp,node_id\n").is_err());
+    }
+
+    const NDJSON: &str = r#"{"timestamp":"2025-01-01T00:00:00Z","node_id":"n1","contaminant":"nitrate","c_in":10,"c_out":2,"flow_q":1}"#;
+
+    #[tokio::test]
+    async fn directory_skips_bad_files_without_losing_good_ones() {
+        let dir = std::env::temp_dir().join(format!("phoenix-feeds-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.ndjson"), NDJSON).unwrap();
+        std::fs::write(dir.join("b.csv"), "timestamp,node_id\nbroken\n").unwrap();
+        std::fs::write(dir.join("c.csv"), CSV).unwrap();
+
+        let mut source = DirectorySource::new(&dir);
+        assert_eq!(source.next_batch().await.unwrap().unwrap().len(), 0);
+        assert_eq!(source.next_batch().await.unwrap().unwrap().len(), 4);
+        std::fs::write(dir.join("d.ndjson"), NDJSON).unwrap();
+        assert_eq!(source.next_batch().await.unwrap().unwrap().len(), 0);
+        assert_eq!(source.next_batch().await.unwrap().unwrap().len(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn directory_retries_files_that_change_and_forgets_removed_ones() {
+        let dir = std::env::temp_dir().join(format!("phoenix-feeds-retry-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let partial = &NDJSON[..NDJSON.len() / 2];
+        std::fs::write(dir.join("a.ndjson"), partial).unwrap();
+
+        let mut source = DirectorySource::new(&dir);
+        assert_eq!(source.next_batch().await.unwrap().unwrap().len(), 0);
+        // Still mid-write when read: it fails, but is not lost.
+        assert_eq!(source.next_batch().await.unwrap().unwrap().len(), 0);
+        std::fs::write(dir.join("a.ndjson"), format!("{NDJSON}\n{NDJSON}")).unwrap();
+        assert_eq!(source.next_batch().await.unwrap().unwrap().len(), 0);
+        assert_eq!(source.next_batch().await.unwrap().unwrap().len(), 2);
+        assert_eq!(source.next_batch().await.unwrap().unwrap().len(), 0);
+
+        std::fs::remove_file(dir.join("a.ndjson")).unwrap();
+        source.next_batch().await.unwrap();
+        assert!(source.files.is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn stdin_skips_malformed_lines() {
+        let input = format!("{NDJSON}\nnot json\n\n{NDJSON}\n{NDJSON}\n");
+        let mut source = StdinSource::from_reader(input.as_bytes(), 2);
+        assert_eq!(source.next_batch().await.unwrap().unwrap().len(), 2);
+        assert_eq!(source.next_batch().await.unwrap().unwrap().len(), 1);
+        assert!(source.next_batch().await.unwrap().is_none());
+    }
 }
@@ -1,21 +1,28 @@
 mod config;
 mod feeds;
-mod state;
 mod shards;
+mod window;
 
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 use anyhow::Result;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use tracing::{error, info};
 use tracing_subscriber::EnvFilter;
 
-use ceim-kernel::{CeimKernel, RegulatoryLimits, TimeSample};
+use ceim_kernel::{
+    AggregationConfig, CeimKernel, ContaminantSeries, LimitsCatalog, MassLoadOptions,
+};
 
-use config::Config;
-use feeds::fetch_samples;
+use ceim_shard_schema::{CeimNodeState, SampleWindow, SHARD_PREFIX};
+use config::{Config, SourceConfig};
+use feeds::{
+    read_samples, DirectorySource, FileSource, HttpSource, ReplaySource, SampleSource, StdinSource,
+};
+use shard_store::ShardStore;
 use shards::write_shard;
-use state::CeimNodeState;
+use window::RollingWindows;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -24,11 +31,16 @@ async fn main() -> Result<()> {
         .init();
 
     let cfg = load_config()?;
-    loop {
-        if let Err(e) = tick(&cfg).await {
-            error!("tick error: {e:?}");
+    match cfg.source()? {
+        SourceConfig::Http { url } => run(&cfg, HttpSource::new(url)).await,
+        SourceConfig::File { path } => run(&cfg, FileSource::new(path)).await,
+        SourceConfig::Directory { path } => run(&cfg, DirectorySource::new(path)).await,
+        SourceConfig::Stdin { max_batch } => run(&cfg, StdinSource::new(max_batch)).await,
+        SourceConfig::Replay { path, speed } => {
+            let step = chrono::Duration::seconds(cfg.poll_interval_seconds as i64);
+            let speed = speed.unwrap_or(f64::INFINITY);
+            run(&cfg, ReplaySource::new(read_samples(&path)?, step, speed)?).await
         }
-        tokio::time::sleep(Duration::from_secs(cfg.poll_interval_seconds)).await;
     }
 }
 
@@ -38,53 +50,143 @@ fn load_config() -> Result<Config> {
     Ok(cfg)
 }
 
-async fn tick(cfg: &Config) -> Result<()> {
+async fn run(cfg: &Config, mut source: impl SampleSource) -> Result<()> {
+    let mut history = RollingWindows::new(chrono::Duration::hours(cfg.lookback_hours));
+    let mut store = ShardStore::open(&cfg.output_dir, SHARD_PREFIX)?;
+    loop {
+        match tick(cfg, &mut source, &mut history, &mut store).await {
+            Ok(true) => {}
+            Ok(false) => {
+                info!("sample source exhausted");
+                return Ok(());
+            }
+            Err(e) => error!("tick error: {e:?}"),
+        }
+        let interval = Duration::from_secs(cfg.poll_interval_seconds);
+        tokio::time::sleep(source.pause(interval)).await;
+    }
+}
+
+/// One poll. Returns `false` once the source is exhausted.
+async fn tick(
+    cfg: &Config,
+    source: &mut impl SampleSource,
+    history: &mut RollingWindows,
+    store: &mut ShardStore,
+) -> Result<bool> {
     info!("fetching water samples");
-    let samples = fetch_samples(&cfg.water_quality_feed_url).await?;
-    let mut nodes = Vec::new();
+    let Some(samples) = source.next_batch().await? else {
+        return Ok(false);
+    };
+    let now = source.now();
+    history.ingest(samples);
+    history.prune(now);
+    let nodes = node_states(history, &cfg.aggregation, now)?;
+    write_shard(store, now, nodes)?;
+    let removed = store.apply_retention(&cfg.retention, now)?;
+    if !removed.is_empty() {
+        info!("retention removed {} shards", removed.len());
+    }
+    Ok(true)
+}
 
-    let groups = group_by_node_and_contaminant(samples);
-    for ((node_id, contaminant), s) in groups {
-        let times: Vec<TimeSample> = s
-            .iter()
-            .enumerate()
-            .map(|(i, sm)| TimeSample {
-                t_hours: i as f64,
-                c_in: sm.c_in,
-                c_out: sm.c_out,
-                flow_q: sm.flow_q,
-            })
-            .collect();
-
-        let limits = RegulatoryLimits {
-            epa: Some(1.0),
-            eu: Some(1.0),
-            who: Some(1.0),
+fn node_states(
+    history: &RollingWindows,
+    aggregation: &AggregationConfig,
+    now: DateTime<Utc>,
+) -> Result<Vec<CeimNodeState>> {
+    let mut by_node: BTreeMap<String, Vec<(ContaminantSeries, SampleWindow)>> = BTreeMap::new();
+    for w in history.windows() {
+        let series = ContaminantSeries {
+            limits: LimitsCatalog::standard().limits_for(&w.contaminant, now.date_naive()),
+            contaminant: w.contaminant,
+            samples: w.samples.iter().map(Into::into).collect(),
         };
-        let impact = CeimKernel::compute(&contaminant, 1.0, &times, &limits);
-        nodes.push(CeimNodeState {
-            node_id: node_id.clone(),
-            contaminant: contaminant.clone(),
-            k_n: impact.k_n,
-            last_updated: Utc::now(),
-        });
+        by_node
+            .entry(w.node_id)
+            .or_default()
+            .push((series, w.window));
     }
 
-    write_shard(&cfg.output_dir, nodes)?;
-    Ok(())
+    let mut nodes = Vec::new();
+    for (node_id, entries) in by_node {
+        let (series, windows): (Vec<_>, Vec<_>) = entries.into_iter().unzip();
+        let aggregate = CeimKernel::aggregate(&series, aggregation, &MassLoadOptions::default())?;
+        for (impact, window) in aggregate.breakdown.into_iter().zip(windows) {
+            nodes.push(CeimNodeState {
+                node_id: node_id.clone(),
+                contaminant: impact.contaminant,
+                k_n: impact.k_n,
+                mass_load_g: Some(impact.mass_load_g),
+                binding_authority: impact.binding.map(|b| b.authority),
+                unregulated: impact.unregulated,
+                ecoimpact_score: aggregate.total_k_n,
+                window: Some(window),
+                last_updated: now,
+            });
+        }
+    }
+    Ok(nodes)
 }
 
-use std::collections::HashMap;
-use feeds::WaterSample;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use feeds::parse_ndjson;
 
-fn group_by_node_and_contaminant(
-    samples: Vec<WaterSample>,
-) -> HashMap<(String, String), Vec<WaterSample>> {
-    let mut map: HashMap<(String, String), Vec<WaterSample>> = HashMap::new();
-    for s in samples {
-        map.entry((s.node_id.clone(), s.contaminant.clone()))
-            .or_default()
-            .push(s);
+    const FEED: &str = r#"
+{"timestamp":"2025-03-01T00:00:00Z","node_id":"n1","contaminant":"nitrate","c_in":40,"c_out":10,"flow_q":2}
+{"timestamp":"2025-03-01T00:20:00Z","node_id":"n1","contaminant":"pfoa","c_in":0.00002,"c_out":0.000001,"flow_q":2}
+{"timestamp":"2025-03-01T00:45:00Z","node_id":"n1","contaminant":"nitrate","c_in":35,"c_out":10,"flow_q":2}
+{"timestamp":"2025-03-01T01:30:00Z","node_id":"n2","contaminant":"nitrate","c_in":20,"c_out":5,"flow_q":1}
+{"timestamp":"2025-03-01T01:40:00Z","node_id":"n1","contaminant":"pfoa","c_in":0.00003,"c_out":0.000001,"flow_q":2}
+"#;
+
+    async fn replay_shards() -> Vec<String> {
+        let samples = parse_ndjson(FEED).unwrap();
+        let mut source =
+            ReplaySource::new(samples, chrono::Duration::minutes(30), f64::INFINITY).unwrap();
+        let mut history = RollingWindows::new(chrono::Duration::hours(1));
+        let mut shards = Vec::new();
+        while let Some(batch) = source.next_batch().await.unwrap() {
+            history.ingest(batch);
+            history.prune(source.now());
+            let nodes = node_states(&history, &AggregationConfig::default(), source.now());
+            shards.push(serde_json::to_string(&nodes.unwrap()).unwrap());
+        }
+        shards
+    }
+
+    /// Contract: what the bridge writes is what the dashboard loader reads.
+    #[tokio::test]
+    async fn dashboard_loads_bridge_shards() {
+        let dir = std::env::temp_dir().join(format!("phoenix-contract-{}", std::process::id()));
+        let mut store = ShardStore::open(&dir, SHARD_PREFIX).unwrap();
+        let mut source = ReplaySource::new(
+            parse_ndjson(FEED).unwrap(),
+            chrono::Duration::hours(1),
+            f64::INFINITY,
+        )
+        .unwrap();
+        let mut history = RollingWindows::new(chrono::Duration::hours(6));
+        let mut last = Vec::new();
+        while let Some(batch) = source.next_batch().await.unwrap() {
+            history.ingest(batch);
+            last = node_states(&history, &AggregationConfig::default(), source.now()).unwrap();
+            write_shard(&mut store, source.now(), last.clone()).unwrap();
+        }
+
+        let loaded = ceim_shard_schema::load_latest(&dir).unwrap().unwrap();
+        assert_eq!(loaded.generated_at, source.now());
+        assert_eq!(loaded.nodes, last);
+        assert!(loaded.nodes.iter().any(|n| n.ecoimpact_score > 0.0));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn replay_reproduces_shards() {
+        let first = replay_shards().await;
+        assert_eq!(first.len(), 5);
+        assert_eq!(first, replay_shards().await);
     }
-    map
 }
@@ -0,0 +1,118 @@
+use std::collections::{BTreeMap, HashMap};
+
+use ceim_kernel::TimeSample;
+use ceim_shard_schema::SampleWindow;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::feeds::WaterSample;
+
+/// One node/contaminant series, ready for the CEIM kernel.
+pub struct WindowedSeries {
+    pub node_id: String,
+    pub contaminant: String,
+    pub window: SampleWindow,
+    /// `t_hours` is measured from `window.start`.
+    pub samples: Vec<TimeSample>,
+}
+
+/// Samples retained across ticks, keyed by node and contaminant and ordered
+/// by timestamp. A repeated timestamp replaces the earlier reading.
+pub struct RollingWindows {
+    lookback: Duration,
+    series: HashMap<(String, String), BTreeMap<DateTime<Utc>, WaterSample>>,
+}
+
+impl RollingWindows {
+    pub fn new(lookback: Duration) -> Self {
+        Self {
+            lookback,
+            series: HashMap::new(),
+        }
+    }
+
+    pub fn ingest(&mut self, samples: Vec<WaterSample>) {
+        for s in samples {
+            self.series
+                .entry((s.node_id.clone(), s.contaminant.clone()))
+                .or_default()
+                .insert(s.timestamp, s);
+        }
+    }
+
+    /// Drop samples older than `now - lookback` and series left empty.
+    pub fn prune(&mut self, now: DateTime<Utc>) {
+        let cutoff = now - self.lookback;
+        self.series.retain(|_, by_time| {
+            *by_time = by_time.split_off(&cutoff);
+            !by_time.is_empty()
+        });
+    }
+
+    pub fn windows(&self) -> Vec<WindowedSeries> {
+        let mut out: Vec<WindowedSeries> = self
+            .series
+            .iter()
+            .filter_map(|((node_id, contaminant), by_time)| {
+                let (&start, _) = by_time.first_key_value()?;
+                let (&end, _) = by_time.last_key_value()?;
+                let samples = by_time
+                    .iter()
+                    .map(|(t, s)| TimeSample {
+                        t_hours: (*t - start).num_milliseconds() as f64 / 3_600_000.0,
+                        c_in: s.c_in,
+                        c_out: s.c_out,
+                        flow_q: s.flow_q,
+                    })
+                    .collect();
+                Some(WindowedSeries {
+                    node_id: node_id.clone(),
+                    contaminant: contaminant.clone(),
+                    window: SampleWindow { start, end },
+                    samples,
+                })
+            })
+            .collect();
+        out.sort_by(|a, b| (&a.node_id, &a.contaminant).cmp(&(&b.node_id, &b.contaminant)));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(minutes: i64, c_in: f64) -> WaterSample {
+        WaterSample {
+            timestamp: DateTime::<Utc>::UNIX_EPOCH + Duration::minutes(minutes),
+            node_id: "n1".into(),
+            contaminant: "nitrate".into(),
+            c_in,
+            c_out: 0.0,
+            flow_q: 1.0,
+        }
+    }
+
+    #[test]
+    fn orders_dedupes_and_uses_real_time() {
+        let mut rolling = RollingWindows::new(Duration::hours(24));
+        rolling.ingest(vec![sample(90, 3.0), sample(0, 1.0), sample(30, 2.0)]);
+        rolling.ingest(vec![sample(30, 2.5)]);
+        let windows = rolling.windows();
+        assert_eq!(windows.len(), 1);
+        let w = &windows[0];
+        let t: Vec<f64> = w.samples.iter().map(|s| s.t_hours).collect();
+        assert_eq!(t, vec![0.0, 0.5, 1.5]);
+        assert_eq!(w.samples[1].c_in, 2.5);
+        assert_eq!(w.window.end - w.window.start, Duration::minutes(90));
+    }
+
+    #[test]
+    fn prunes_outside_lookback() {
+        let mut rolling = RollingWindows::new(Duration::hours(1));
+        rolling.ingest(vec![sample(0, 1.0), sample(45, 1.0), sample(120, 1.0)]);
+        rolling.prune(DateTime::<Utc>::UNIX_EPOCH + Duration::minutes(120));
+        assert_eq!(rolling.windows()[0].samples.len(), 1);
+        rolling.prune(DateTime::<Utc>::UNIX_EPOCH + Duration::minutes(300));
+        assert!(rolling.windows().is_empty());
+    }
+}
@@ -0,0 +1,311 @@
+//! Single evaluator for neuromorph capability reversal.
+//!
+//! The thresholds and the `canrevertcapability` condition come from
+//! `aln/reversal_policy.aln`; governance-core, morpheus-core and
+//! neuromorph-sim delegate to this crate instead of re-deriving the rule.
+
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The checked-in reversal policy.
+pub const REVERSAL_POLICY_ALN: &str = include_str!("../../../aln/reversal_policy.aln");
+
+#[derive(Debug, Error, PartialEq)]
+pub enum PolicyError {
+    #[error("line {line}: {reason}")]
+    Syntax { line: usize, reason: String },
+    #[error("missing policy row `{0}`")]
+    MissingRow(&'static str),
+    #[error("invalid value `{value}` for `{name}`")]
+    InvalidValue { name: String, value: String },
+    #[error("unknown term `{0}` in canrevertcapability")]
+    UnknownTerm(String),
+}
+
+/// Variables the `canrevertcapability` condition may reference.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PolicyVariable {
+    LifeHarmFlag,
+    NeuromorphGodSatisfied,
+    ExplicitReversalOrder,
+    NoSaferAlternative,
+}
+
+impl PolicyVariable {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "lifeharmflag" => Some(Self::LifeHarmFlag),
+            "neuromorphgodsatisfied" => Some(Self::NeuromorphGodSatisfied),
+            "explicitreversalorder" => Some(Self::ExplicitReversalOrder),
+            "nosaferalternative" => Some(Self::NoSaferAlternative),
+            _ => None,
+        }
+    }
+}
+
+/// One `AND` term: `variable` must equal `expected`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyTerm {
+    pub variable: PolicyVariable,
+    pub expected: bool,
+}
+
+/// Evidence for the derived `nosaferalternative` flag.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SaferAlternativeEvidence {
+    pub mitigations_exhausted: bool,
+    pub roh: f64,
+    pub decay: f64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReversalInputs {
+    pub life_harm_flag: bool,
+    pub neuromorph_god_satisfied: bool,
+    /// Owner-signed explicit reversal order.
+    pub explicit_reversal_order: bool,
+    pub no_safer_alternative: bool,
+}
+
+impl ReversalInputs {
+    fn value(&self, variable: PolicyVariable) -> bool {
+        match variable {
+            PolicyVariable::LifeHarmFlag => self.life_harm_flag,
+            PolicyVariable::NeuromorphGodSatisfied => self.neuromorph_god_satisfied,
+            PolicyVariable::ExplicitReversalOrder => self.explicit_reversal_order,
+            PolicyVariable::NoSaferAlternative => self.no_safer_alternative,
+        }
+    }
+}
+
+/// Outcome of the reversal gate; a denial names the first failing term.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReversalVerdict {
+    Granted,
+    DeniedLifeHarmFlag,
+    DeniedNeuromorphGodUnsatisfied,
+    DeniedExplicitOrderMissing,
+    DeniedNoSaferAlternativeNotProved,
+}
+
+impl ReversalVerdict {
+    pub fn is_granted(self) -> bool {
+        self == Self::Granted
+    }
+
+    fn denied_by(variable: PolicyVariable) -> Self {
+        match variable {
+            PolicyVariable::LifeHarmFlag => Self::DeniedLifeHarmFlag,
+            PolicyVariable::NeuromorphGodSatisfied => Self::DeniedNeuromorphGodUnsatisfied,
+            PolicyVariable::ExplicitReversalOrder => Self::DeniedExplicitOrderMissing,
+            PolicyVariable::NoSaferAlternative => Self::DeniedNoSaferAlternativeNotProved,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReversalPolicy {
+    /// Non-waivable default: evolution is monotone unless the gate grants.
+    pub allow_neuromorph_reversal: bool,
+    /// RoH must stay strictly above this for `nosaferalternative`.
+    pub roh_min: f64,
+    /// Decay must stay strictly above this for `nosaferalternative`.
+    pub decay_min: f64,
+    /// `canrevertcapability`, in file order.
+    pub condition: Vec<PolicyTerm>,
+}
+
+impl ReversalPolicy {
+    /// Policy parsed from the checked-in ALN file.
+    pub fn standard() -> &'static ReversalPolicy {
+        static POLICY: OnceLock<ReversalPolicy> = OnceLock::new();
+        POLICY.get_or_init(|| {
+            Self::parse(REVERSAL_POLICY_ALN).expect("aln/reversal_policy.aln is valid")
+        })
+    }
+
+    /// Parse a `REVERSAL-POLICY` section. Lines that do not start with `ROW`
+    /// or `SECTION` continue the previous row.
+    pub fn parse(text: &str) -> Result<Self, PolicyError> {
+        let mut rows: Vec<(usize, String)> = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            if trimmed.starts_with("SECTION,") {
+                if trimmed != "SECTION,REVERSAL-POLICY" {
+                    return Err(PolicyError::Syntax {
+                        line: i + 1,
+                        reason: format!("unexpected section `{trimmed}`"),
+                    });
+                }
+            } else if trimmed.starts_with("ROW,") {
+                rows.push((i + 1, trimmed.to_string()));
+            } else if let Some((_, row)) = rows.last_mut() {
+                row.push_str(trimmed);
+            } else {
+                return Err(PolicyError::Syntax {
+                    line: i + 1,
+                    reason: "continuation before any ROW".to_string(),
+                });
+            }
+        }
+
+        let mut allow = None;
+        let mut roh_min = None;
+        let mut decay_min = None;
+        let mut condition = None;
+        for (line, row) in &rows {
+            let fields: Vec<&str> = row.splitn(9, ',').map(str::trim).collect();
+            if fields.len() < 8 {
+                return Err(PolicyError::Syntax {
+                    line: *line,
+                    reason: "expected ROW,rev,<scope>,<kind>,<name>,<value>,<type>,<mode>"
+                        .to_string(),
+                });
+            }
+            let (name, value) = (fields[4], fields[5]);
+            let invalid = || PolicyError::InvalidValue {
+                name: name.to_string(),
+                value: value.to_string(),
+            };
+            match name {
+                "allowneuromorphreversal" => {
+                    allow = Some(value.parse::<bool>().map_err(|_| invalid())?)
+                }
+                "nosaferalternative.roh_min" => {
+                    roh_min = Some(value.parse::<f64>().map_err(|_| invalid())?)
+                }
+                "nosaferalternative.decay_min" => {
+                    decay_min = Some(value.parse::<f64>().map_err(|_| invalid())?)
+                }
+                "canrevertcapability" => condition = Some(parse_condition(value)?),
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            allow_neuromorph_reversal: allow
+                .ok_or(PolicyError::MissingRow("allowneuromorphreversal"))?,
+            roh_min: roh_min.ok_or(PolicyError::MissingRow("nosaferalternative.roh_min"))?,
+            decay_min: decay_min.ok_or(PolicyError::MissingRow("nosaferalternative.decay_min"))?,
+            condition: condition.ok_or(PolicyError::MissingRow("canrevertcapability"))?,
+        })
+    }
+
+    /// Derived flag: mitigations are exhausted and RoH and decay persist high.
+    pub fn no_safer_alternative(&self, evidence: &SaferAlternativeEvidence) -> bool {
+        evidence.mitigations_exhausted
+            && evidence.roh > self.roh_min
+            && evidence.decay > self.decay_min
+    }
+
+    /// Evaluate `canrevertcapability`; the first failing term decides. When
+    /// `allow_neuromorph_reversal` is set, reversal no longer needs the
+    /// emergency terms and only the life-harm terms still apply.
+    pub fn evaluate(&self, inputs: &ReversalInputs) -> ReversalVerdict {
+        self.condition
+            .iter()
+            .filter(|term| {
+                !self.allow_neuromorph_reversal || term.variable == PolicyVariable::LifeHarmFlag
+            })
+            .find(|term| inputs.value(term.variable) != term.expected)
+            .map_or(ReversalVerdict::Granted, |term| {
+                ReversalVerdict::denied_by(term.variable)
+            })
+    }
+}
+
+fn parse_condition(expr: &str) -> Result<Vec<PolicyTerm>, PolicyError> {
+    expr.split(" AND ")
+        .map(|term| {
+            let mut words = term.split_whitespace();
+            let name = words.next().unwrap_or_default();
+            let variable = PolicyVariable::from_name(name)
+                .ok_or_else(|| PolicyError::UnknownTerm(term.trim().to_string()))?;
+            let expected = match (words.next(), words.next()) {
+                (None, _) | (Some("true"), None) => true,
+                (Some("false"), None) => false,
+                _ => return Err(PolicyError::UnknownTerm(term.trim().to_string())),
+            };
+            Ok(PolicyTerm { variable, expected })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standard_policy_matches_file() {
+        let policy = ReversalPolicy::standard();
+        assert!(!policy.allow_neuromorph_reversal);
+        assert_eq!(policy.roh_min, 0.3);
+        assert_eq!(policy.decay_min, 0.8);
+        assert_eq!(policy.condition.len(), 4);
+        assert_eq!(
+            policy.condition[0],
+            PolicyTerm {
+                variable: PolicyVariable::LifeHarmFlag,
+                expected: false
+            }
+        );
+    }
+
+    #[test]
+    fn first_failing_term_decides() {
+        let policy = ReversalPolicy::standard();
+        let mut inputs = ReversalInputs {
+            life_harm_flag: false,
+            neuromorph_god_satisfied: true,
+            explicit_reversal_order: true,
+            no_safer_alternative: true,
+        };
+        assert_eq!(policy.evaluate(&inputs), ReversalVerdict::Granted);
+        inputs.no_safer_alternative = false;
+        inputs.explicit_reversal_order = false;
+        assert_eq!(
+            policy.evaluate(&inputs),
+            ReversalVerdict::DeniedExplicitOrderMissing
+        );
+        inputs.life_harm_flag = true;
+        assert_eq!(
+            policy.evaluate(&inputs),
+            ReversalVerdict::DeniedLifeHarmFlag
+        );
+    }
+
+    #[test]
+    fn allowed_reversal_only_checks_life_harm() {
+        let text = REVERSAL_POLICY_ALN.replace(
+            "allowneuromorphreversal,false,",
+            "allowneuromorphreversal,true,",
+        );
+        let policy = ReversalPolicy::parse(&text).unwrap();
+        assert!(policy.allow_neuromorph_reversal);
+        let mut inputs = ReversalInputs::default();
+        assert_eq!(policy.evaluate(&inputs), ReversalVerdict::Granted);
+        assert_eq!(
+            ReversalPolicy::standard().evaluate(&inputs),
+            ReversalVerdict::DeniedNeuromorphGodUnsatisfied
+        );
+        inputs.life_harm_flag = true;
+        assert_eq!(
+            policy.evaluate(&inputs),
+            ReversalVerdict::DeniedLifeHarmFlag
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_terms() {
+        let text = REVERSAL_POLICY_ALN.replace("nosaferalternative true,", "vibes true,");
+        assert_eq!(
+            ReversalPolicy::parse(&text),
+            Err(PolicyError::UnknownTerm("vibes true".to_string()))
+        );
+    }
+}
@@ -0,0 +1,7 @@
+mod manifest;
+mod retention;
+mod store;
+
+pub use manifest::{Manifest, ShardEntry};
+pub use retention::{Compaction, RetentionPolicy};
+pub use store::{ShardStore, ShardStoreError};
@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ShardEntry {
+    /// File name relative to the store directory.
+    pub file: String,
+    pub generated_at: DateTime<Utc>,
+    /// Hex SHA-256 of the file contents.
+    pub sha256: String,
+    pub bytes: u64,
+}
+
+/// Index of the shards in a store, ordered by `generated_at`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Manifest {
+    pub version: u32,
+    pub shards: Vec<ShardEntry>,
+}
+
+impl Manifest {
+    pub const VERSION: u32 = 1;
+
+    pub fn empty() -> Self {
+        Self {
+            version: Self::VERSION,
+            shards: Vec::new(),
+        }
+    }
+
+    /// Insert keeping the order; an entry for the same file is replaced.
+    pub(crate) fn insert(&mut self, entry: ShardEntry) {
+        self.shards.retain(|e| e.file != entry.file);
+        let at = self
+            .shards
+            .partition_point(|e| e.generated_at <= entry.generated_at);
+        self.shards.insert(at, entry);
+    }
+}
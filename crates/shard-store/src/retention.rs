@@ -0,0 +1,62 @@
+use std::collections::BTreeSet;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::ShardEntry;
+
+/// Thin out shards older than `older_than_hours` to the newest one in each
+/// `bucket_hours` interval.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Compaction {
+    pub older_than_hours: i64,
+    pub bucket_hours: i64,
+}
+
+/// Applied in order: compaction, then age, then count.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    #[serde(default)]
+    pub max_age_hours: Option<i64>,
+    /// Keep at most this many of the newest shards.
+    #[serde(default)]
+    pub max_shards: Option<usize>,
+    #[serde(default)]
+    pub compaction: Option<Compaction>,
+}
+
+impl RetentionPolicy {
+    /// Indices into `shards` (ordered oldest first) that the policy drops.
+    pub(crate) fn expired(&self, shards: &[ShardEntry], now: DateTime<Utc>) -> BTreeSet<usize> {
+        let mut drop = BTreeSet::new();
+
+        if let Some(c) = self.compaction.filter(|c| c.bucket_hours > 0) {
+            let cutoff = now - Duration::hours(c.older_than_hours);
+            let bucket_secs = c.bucket_hours * 3600;
+            let bucket = |e: &ShardEntry| e.generated_at.timestamp().div_euclid(bucket_secs);
+            for (i, pair) in shards.windows(2).enumerate() {
+                if pair[0].generated_at < cutoff && bucket(&pair[0]) == bucket(&pair[1]) {
+                    drop.insert(i);
+                }
+            }
+        }
+
+        if let Some(hours) = self.max_age_hours {
+            let cutoff = now - Duration::hours(hours);
+            drop.extend(
+                shards
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, e)| e.generated_at < cutoff)
+                    .map(|(i, _)| i),
+            );
+        }
+
+        if let Some(max) = self.max_shards {
+            let kept: Vec<usize> = (0..shards.len()).filter(|i| !drop.contains(i)).collect();
+            let excess = kept.len().saturating_sub(max);
+            drop.extend(kept.into_iter().take(excess));
+        }
+        drop
+    }
+}
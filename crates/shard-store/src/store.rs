@@ -0,0 +1,382 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::{Manifest, RetentionPolicy, ShardEntry};
+
+#[derive(Debug, Error)]
+pub enum ShardStoreError {
+    #[error("{path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("shard {file} digest mismatch: manifest {expected}, file {actual}")]
+    DigestMismatch {
+        file: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("unsupported manifest version {0}")]
+    UnsupportedManifest(u32),
+}
+
+fn io_err(path: &Path) -> impl FnOnce(io::Error) -> ShardStoreError + '_ {
+    move |source| ShardStoreError::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+/// Write through a temp file and rename, so readers never see partial files.
+fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), ShardStoreError> {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("shard");
+    let tmp = path.with_file_name(format!(".{name}.tmp"));
+    let mut file = fs::File::create(&tmp).map_err(io_err(&tmp))?;
+    file.write_all(bytes).map_err(io_err(&tmp))?;
+    file.sync_all().map_err(io_err(&tmp))?;
+    fs::rename(&tmp, path).map_err(io_err(path))
+}
+
+/// A directory of JSON shards indexed by `manifest.json`. Opening the store
+/// adopts `<prefix>_*.json` files the manifest does not list, such as shards
+/// written before the manifest existed; other files are ignored.
+pub struct ShardStore {
+    dir: PathBuf,
+    prefix: String,
+    manifest: Manifest,
+}
+
+impl ShardStore {
+    pub const MANIFEST: &'static str = "manifest.json";
+
+    /// Open or create a store; shards are written as `<prefix>_<time>.json`.
+    pub fn open(dir: impl Into<PathBuf>, prefix: &str) -> Result<Self, ShardStoreError> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir).map_err(io_err(&dir))?;
+        let mut store = Self {
+            dir,
+            prefix: prefix.to_string(),
+            manifest: Manifest::empty(),
+        };
+        store.refresh()?;
+        if store.adopt_unlisted()? > 0 {
+            store.save_manifest()?;
+        }
+        Ok(store)
+    }
+
+    /// Open a store for reading without creating or writing anything:
+    /// unlisted shards are adopted in memory only, and a missing directory
+    /// reads as an empty store.
+    pub fn open_existing(dir: impl Into<PathBuf>, prefix: &str) -> Result<Self, ShardStoreError> {
+        let mut store = Self {
+            dir: dir.into(),
+            prefix: prefix.to_string(),
+            manifest: Manifest::empty(),
+        };
+        if !store.dir.is_dir() {
+            return Ok(store);
+        }
+        store.refresh()?;
+        store.adopt_unlisted()?;
+        Ok(store)
+    }
+
+    /// Index `<prefix>_*.json` files missing from the manifest by their
+    /// `generated_at`. Files without a readable RFC 3339 `generated_at` stay
+    /// unlisted. Returns how many were adopted.
+    fn adopt_unlisted(&mut self) -> Result<usize, ShardStoreError> {
+        let start = format!("{}_", self.prefix);
+        let mut adopted = 0;
+        for dir_entry in fs::read_dir(&self.dir).map_err(io_err(&self.dir))? {
+            let path = dir_entry.map_err(io_err(&self.dir))?.path();
+            let Some(file) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if !file.starts_with(&start)
+                || !file.ends_with(".json")
+                || self.manifest.shards.iter().any(|e| e.file == file)
+            {
+                continue;
+            }
+            let raw = fs::read(&path).map_err(io_err(&path))?;
+            let generated_at = serde_json::from_slice::<serde_json::Value>(&raw)
+                .ok()
+                .and_then(|v| v.get("generated_at")?.as_str()?.parse().ok());
+            let Some(generated_at) = generated_at else {
+                continue;
+            };
+            self.manifest.insert(ShardEntry {
+                file: file.to_string(),
+                generated_at,
+                sha256: hex::encode(Sha256::digest(&raw)),
+                bytes: raw.len() as u64,
+            });
+            adopted += 1;
+        }
+        Ok(adopted)
+    }
+
+    /// Reload the manifest, picking up shards written by another process.
+    pub fn refresh(&mut self) -> Result<(), ShardStoreError> {
+        let path = self.dir.join(Self::MANIFEST);
+        self.manifest = match fs::read(&path) {
+            Ok(raw) => serde_json::from_slice(&raw)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Manifest::empty(),
+            Err(e) => return Err(io_err(&path)(e)),
+        };
+        if self.manifest.version != Manifest::VERSION {
+            return Err(ShardStoreError::UnsupportedManifest(self.manifest.version));
+        }
+        Ok(())
+    }
+
+    pub fn entries(&self) -> &[ShardEntry] {
+        &self.manifest.shards
+    }
+
+    fn save_manifest(&self) -> Result<(), ShardStoreError> {
+        let json = serde_json::to_vec_pretty(&self.manifest)?;
+        write_atomic(&self.dir.join(Self::MANIFEST), &json)
+    }
+
+    pub fn write<T: Serialize>(
+        &mut self,
+        generated_at: DateTime<Utc>,
+        shard: &T,
+    ) -> Result<ShardEntry, ShardStoreError> {
+        let json = serde_json::to_vec_pretty(shard)?;
+        let file = format!(
+            "{}_{}.json",
+            self.prefix,
+            generated_at.format("%Y%m%dT%H%M%S%.3fZ")
+        );
+        write_atomic(&self.dir.join(&file), &json)?;
+        let entry = ShardEntry {
+            file,
+            generated_at,
+            sha256: hex::encode(Sha256::digest(&json)),
+            bytes: json.len() as u64,
+        };
+        self.manifest.insert(entry.clone());
+        self.save_manifest()?;
+        Ok(entry)
+    }
+
+    /// Read a shard, checking it against the manifest digest.
+    pub fn read<T: DeserializeOwned>(&self, entry: &ShardEntry) -> Result<T, ShardStoreError> {
+        let path = self.dir.join(&entry.file);
+        let raw = fs::read(&path).map_err(io_err(&path))?;
+        let actual = hex::encode(Sha256::digest(&raw));
+        if actual != entry.sha256 {
+            return Err(ShardStoreError::DigestMismatch {
+                file: entry.file.clone(),
+                expected: entry.sha256.clone(),
+                actual,
+            });
+        }
+        Ok(serde_json::from_slice(&raw)?)
+    }
+
+    pub fn latest<T: DeserializeOwned>(&self) -> Result<Option<(ShardEntry, T)>, ShardStoreError> {
+        self.entries()
+            .last()
+            .map(|e| Ok((e.clone(), self.read(e)?)))
+            .transpose()
+    }
+
+    /// Entries with `from <= generated_at < to`.
+    pub fn in_range(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> &[ShardEntry] {
+        let shards = self.entries();
+        let start = shards.partition_point(|e| e.generated_at < from);
+        let end = shards.partition_point(|e| e.generated_at < to).max(start);
+        &shards[start..end]
+    }
+
+    pub fn read_range<T: DeserializeOwned>(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<(ShardEntry, T)>, ShardStoreError> {
+        self.in_range(from, to)
+            .iter()
+            .map(|e| Ok((e.clone(), self.read(e)?)))
+            .collect()
+    }
+
+    /// Drop shards the policy expires. The manifest is updated before files
+    /// are deleted, so a crash leaves unlisted files rather than dangling
+    /// entries; the next `open` re-adopts them and retention expires them
+    /// again.
+    pub fn apply_retention(
+        &mut self,
+        policy: &RetentionPolicy,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<ShardEntry>, ShardStoreError> {
+        let expired = policy.expired(&self.manifest.shards, now);
+        if expired.is_empty() {
+            return Ok(Vec::new());
+        }
+        let (removed, kept): (Vec<_>, Vec<_>) = self
+            .manifest
+            .shards
+            .drain(..)
+            .enumerate()
+            .partition(|(i, _)| expired.contains(i));
+        self.manifest.shards = kept.into_iter().map(|(_, e)| e).collect();
+        self.save_manifest()?;
+
+        let removed: Vec<ShardEntry> = removed.into_iter().map(|(_, e)| e).collect();
+        for entry in &removed {
+            let path = self.dir.join(&entry.file);
+            match fs::remove_file(&path) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(io_err(&path)(e)),
+                _ => {}
+            }
+        }
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Compaction;
+    use chrono::Duration;
+    use serde::Deserialize;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Shard {
+        n: u32,
+    }
+
+    fn temp_store(name: &str) -> ShardStore {
+        let dir = std::env::temp_dir().join(format!(
+            "shard-store-{name}-{}-{}",
+            std::process::id(),
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        ShardStore::open(dir, "ceim_shard").unwrap()
+    }
+
+    fn at(hours: i64) -> DateTime<Utc> {
+        DateTime::<Utc>::UNIX_EPOCH + Duration::hours(hours)
+    }
+
+    #[test]
+    fn indexes_and_reads_by_range() {
+        let mut store = temp_store("range");
+        for h in [3, 1, 2] {
+            store.write(at(h), &Shard { n: h as u32 }).unwrap();
+        }
+        fs::write(store.dir.join("notes.txt"), "not a shard").unwrap();
+
+        let reopened = ShardStore::open(&store.dir, "ceim_shard").unwrap();
+        let (_, latest): (_, Shard) = reopened.latest().unwrap().unwrap();
+        assert_eq!(latest, Shard { n: 3 });
+        let range: Vec<(ShardEntry, Shard)> = reopened.read_range(at(1), at(3)).unwrap();
+        assert_eq!(
+            range.into_iter().map(|(_, s)| s.n).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        fs::remove_dir_all(&store.dir).unwrap();
+    }
+
+    #[test]
+    fn adopts_shards_written_without_a_manifest() {
+        let mut store = temp_store("adopt");
+        store.write(at(2), &Shard { n: 2 }).unwrap();
+        // As the baseline bridge named and wrote them: no manifest entry.
+        fs::write(
+            store.dir.join("ceim_shard_1970-01-01T01_00_00+00_00.json"),
+            r#"{"generated_at": "1970-01-01T01:00:00+00:00", "n": 1}"#,
+        )
+        .unwrap();
+        fs::write(store.dir.join("ceim_shard_broken.json"), "{").unwrap();
+
+        let reopened = ShardStore::open(&store.dir, "ceim_shard").unwrap();
+        let hours: Vec<i64> = reopened
+            .entries()
+            .iter()
+            .map(|e| e.generated_at.timestamp() / 3600)
+            .collect();
+        assert_eq!(hours, vec![1, 2]);
+        let first: Shard = reopened.read(&reopened.entries()[0]).unwrap();
+        assert_eq!(first, Shard { n: 1 });
+        // The adoption was persisted.
+        let manifest = fs::read_to_string(store.dir.join(ShardStore::MANIFEST)).unwrap();
+        assert!(manifest.contains("ceim_shard_1970-01-01T01_00_00+00_00.json"));
+        fs::remove_dir_all(&store.dir).unwrap();
+    }
+
+    #[test]
+    fn open_existing_writes_nothing() {
+        let dir = std::env::temp_dir().join(format!("shard-store-ro-{}", std::process::id()));
+        let missing = ShardStore::open_existing(&dir, "ceim_shard").unwrap();
+        assert!(missing.entries().is_empty());
+        assert!(!dir.exists());
+
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("ceim_shard_legacy.json"),
+            r#"{"generated_at": "1970-01-01T01:00:00Z", "n": 1}"#,
+        )
+        .unwrap();
+        let store = ShardStore::open_existing(&dir, "ceim_shard").unwrap();
+        assert_eq!(store.entries().len(), 1);
+        assert!(!dir.join(ShardStore::MANIFEST).exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn detects_tampering() {
+        let mut store = temp_store("digest");
+        let entry = store.write(at(0), &Shard { n: 1 }).unwrap();
+        fs::write(store.dir.join(&entry.file), r#"{"n": 2}"#).unwrap();
+        assert!(matches!(
+            store.read::<Shard>(&entry),
+            Err(ShardStoreError::DigestMismatch { .. })
+        ));
+        fs::remove_dir_all(&store.dir).unwrap();
+    }
+
+    #[test]
+    fn retention_compacts_then_caps() {
+        let mut store = temp_store("retention");
+        for h in 0..10 {
+            store.write(at(h), &Shard { n: h as u32 }).unwrap();
+        }
+        let policy = RetentionPolicy {
+            max_age_hours: None,
+            max_shards: Some(5),
+            compaction: Some(Compaction {
+                older_than_hours: 4,
+                bucket_hours: 3,
+            }),
+        };
+        // Hours 0-5 are older than the cutoff; buckets [0,3) and [3,6) keep
+        // hours 2 and 5. The cap then drops hour 2.
+        let removed = store.apply_retention(&policy, at(10)).unwrap();
+        assert_eq!(removed.len(), 5);
+        let kept: Vec<i64> = store
+            .entries()
+            .iter()
+            .map(|e| e.generated_at.timestamp() / 3600)
+            .collect();
+        assert_eq!(kept, vec![5, 6, 7, 8, 9]);
+        for entry in &removed {
+            assert!(!store.dir.join(&entry.file).exists());
+        }
+        fs::remove_dir_all(&store.dir).unwrap();
+    }
+}
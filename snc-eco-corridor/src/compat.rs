@@ -0,0 +1,637 @@
+//! Conversions from legacy corridor context shapes into the canonical
+//! [`EcoCorridorContext`].
+//!
+//! Two legacy shapes are still on the wire:
+//! - the snake_case client shape [`ClientCorridorContext`] (string id, 0–1 eco
+//!   scores, [`ClientFpicIdsStatus`] enum), which `morpheus-client` re-exports
+//!   as its `types::corridor` types, and
+//! - `eco-corridor-context.v1` (`eco_corridor_context.v1.json`, `EcoCorridorContext.kt`).
+//!
+//! Neither carries tier, strength, guardrails or the HGO capsule, so those come
+//! from the corridor's loaded [`CorridorProfile`]. Every legacy field that has
+//! no canonical counterpart is returned in [`Converted::unmapped`] with its
+//! original value; nothing is dropped silently.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::loader::{CorridorProfile, CorridorRegistry};
+use crate::{EcoCorridorContext, EcoGuardrails, FpicIdsState, CORRIDOR_CONTEXT_SCHEMA};
+
+/// Schema tag of the camelCase v1 shape.
+pub const CORRIDOR_CONTEXT_SCHEMA_V1: &str = "eco-corridor-context.v1";
+
+#[derive(Debug, Error)]
+pub enum CompatError {
+    #[error("corridor context is not valid JSON for its shape: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("unknown corridor context version {0}")]
+    UnknownVersion(String),
+    #[error("cannot tell which corridor context shape this is")]
+    UnknownShape,
+    #[error("no corridor profile loaded for {0}")]
+    UnknownCorridor(String),
+    #[error("fpic consent subject {subject} does not match corridor {corridor}")]
+    ConsentSubjectMismatch { subject: String, corridor: String },
+}
+
+/// A legacy field with no canonical counterpart, kept verbatim.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct UnmappedField {
+    pub field: String,
+    pub value: Value,
+    pub reason: String,
+}
+
+impl UnmappedField {
+    fn new(field: &str, value: impl Serialize, reason: &str) -> Self {
+        Self {
+            field: field.to_string(),
+            value: serde_json::to_value(value).unwrap_or(Value::Null),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+/// Result of converting a corridor context into the canonical model.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Converted {
+    pub context: EcoCorridorContext,
+    pub unmapped: Vec<UnmappedField>,
+}
+
+impl Converted {
+    pub fn is_lossless(&self) -> bool {
+        self.unmapped.is_empty()
+    }
+}
+
+/// Ecological corridor context as `morpheus-client` carries it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ClientCorridorContext {
+    /// Corridor code, e.g. `tribal.gric-epa-2024`.
+    pub corridor_id: String,
+    /// Human-readable name
+    pub corridor_name: String,
+    /// Ecological impact metrics for this corridor
+    pub eco_impact: ClientEcoImpactMetrics,
+    /// FPIC/IDS consent status
+    pub fpic_ids_status: ClientFpicIdsStatus,
+    /// Jurisdiction(s) this corridor operates under (e.g., "Phoenix_medical", "Chile")
+    pub jurisdictions: Vec<String>,
+    /// Timestamp of last update (ISO 8601)
+    pub last_updated: String,
+    /// Optional notes for human review
+    pub notes: Option<String>,
+}
+
+impl ClientCorridorContext {
+    /// Create a context with no consent and no jurisdictions.
+    pub fn new(corridor_id: String, corridor_name: String) -> Self {
+        Self {
+            corridor_id,
+            corridor_name,
+            eco_impact: ClientEcoImpactMetrics::default(),
+            fpic_ids_status: ClientFpicIdsStatus::NotObtained,
+            jurisdictions: Vec::new(),
+            last_updated: chrono::Utc::now().to_rfc3339(),
+            notes: None,
+        }
+    }
+
+    /// Validate the corridor context
+    pub fn validate(&self) -> Result<(), String> {
+        if self.corridor_id.is_empty() {
+            return Err("Corridor ID cannot be empty".to_string());
+        }
+        if self.corridor_name.is_empty() {
+            return Err("Corridor name cannot be empty".to_string());
+        }
+        if self.jurisdictions.is_empty() {
+            return Err("At least one jurisdiction must be specified".to_string());
+        }
+        if !self.fpic_permits_operation() {
+            return Err(format!(
+                "Cannot operate without granted FPIC/IDS (status {:?})",
+                self.fpic_ids_status
+            ));
+        }
+        if !self.eco_impact.is_admissible() {
+            return Err("Ecological impact exceeds admissible thresholds".to_string());
+        }
+        Ok(())
+    }
+
+    /// Only `Granted` and `Conditional` consent can permit an operation;
+    /// the client checks conditions per operation.
+    pub fn fpic_permits_operation(&self) -> bool {
+        matches!(
+            self.fpic_ids_status,
+            ClientFpicIdsStatus::Granted | ClientFpicIdsStatus::Conditional(_)
+        )
+    }
+
+    /// Check if this corridor permits operation (basic gate)
+    pub fn is_operational(&self) -> bool {
+        self.fpic_permits_operation()
+            && self.eco_impact.is_admissible()
+            && !self.jurisdictions.is_empty()
+    }
+}
+
+/// Ecological impact scores (0.0–1.0, where 0 = no impact, 1 = maximal).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ClientEcoImpactMetrics {
+    /// Climate impact score
+    pub climate_impact: f64,
+    /// Biodiversity impact score
+    pub biodiversity_impact: f64,
+    /// Biosphere resilience score (0 = resilient, 1 = fragile)
+    pub biosphere_fragility: f64,
+    /// Corridor safety score (how protected the corridor is)
+    pub corridor_safety: f64,
+    /// Service impact (e.g., water, soil, air quality)
+    pub service_impact: f64,
+}
+
+impl ClientEcoImpactMetrics {
+    /// Check if all metrics are within admissible bounds
+    pub fn is_admissible(&self) -> bool {
+        self.climate_impact <= 0.3
+            && self.biodiversity_impact <= 0.3
+            && self.biosphere_fragility <= 0.25
+            && self.corridor_safety >= 0.7
+            && self.service_impact <= 0.25
+    }
+
+    /// Compute composite ecological risk (0.0–1.0)
+    pub fn composite_risk(&self) -> f64 {
+        let mean = (self.climate_impact
+            + self.biodiversity_impact
+            + self.biosphere_fragility
+            + (1.0 - self.corridor_safety)
+            + self.service_impact)
+            / 5.0;
+        mean.clamp(0.0, 1.0)
+    }
+}
+
+/// Free, Prior, and Informed Consent / Indigenous Data Sovereignty state
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ClientFpicIdsStatus {
+    /// Consent not yet obtained
+    NotObtained,
+    /// Consent granted
+    Granted,
+    /// Consent granted with conditions
+    Conditional(Vec<String>),
+    /// Consent revoked
+    Revoked,
+    /// FPIC pending (in process)
+    Pending,
+}
+
+/// `eco-corridor-context.v1` as described by `eco_corridor_context.v1.json`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CorridorContextV1 {
+    pub version: String,
+    pub corridor_id: String,
+    pub eco_impact: EcoImpactV1,
+    pub fpic_ids_state: Option<VerifiableConsentV1>,
+    pub neurorights_capsule: NeurorightsCapsuleV1,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EcoImpactV1 {
+    pub soil_health: f64,
+    pub water_quality: f64,
+    pub microbiome_diversity: f64,
+    pub corridor_resilience: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiableConsentV1 {
+    pub issuer_did: String,
+    pub subject_corridor_id: String,
+    pub status: ConsentStatusV1,
+    pub issued_at: String,
+    pub revoked_at: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ConsentStatusV1 {
+    Granted,
+    Revoked,
+    Pending,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NeurorightsCapsuleV1 {
+    pub flags: Vec<String>,
+}
+
+const NO_ABSOLUTE_DELTAS: &str =
+    "normalized 0-1 scores do not convert to absolute eco deltas; canonical eco left unset";
+
+fn resolve<'a>(
+    registry: &'a CorridorRegistry,
+    corridor_id: &str,
+) -> Result<&'a CorridorProfile, CompatError> {
+    registry
+        .latest(corridor_id)
+        .ok_or_else(|| CompatError::UnknownCorridor(corridor_id.to_string()))
+}
+
+fn base_context(profile: &CorridorProfile) -> EcoCorridorContext {
+    EcoCorridorContext {
+        version: CORRIDOR_CONTEXT_SCHEMA.to_string(),
+        corridor_id: profile.id.clone(),
+        display_name: None,
+        strength: profile.strength.clone(),
+        fpic: profile.fpic.initial_state(),
+        neurorights: profile.neurorights.clone(),
+        eco: None,
+        eco_guardrails: profile.eco_guardrails.clone(),
+        jurisdiction_profile_id: None,
+    }
+}
+
+/// Convert the `morpheus-client` shape. Only `Granted` maps to a granted
+/// canonical state; every other status is recorded in `unmapped`, since the
+/// canonical `fpic_granted` flag cannot tell them apart.
+pub fn from_client(
+    legacy: &ClientCorridorContext,
+    registry: &CorridorRegistry,
+) -> Result<Converted, CompatError> {
+    let profile = resolve(registry, &legacy.corridor_id)?;
+    let mut context = base_context(profile);
+    let mut unmapped = Vec::new();
+
+    context.display_name = Some(legacy.corridor_name.clone());
+    unmapped.push(UnmappedField::new(
+        "eco_impact",
+        &legacy.eco_impact,
+        NO_ABSOLUTE_DELTAS,
+    ));
+
+    let (granted, note) = match &legacy.fpic_ids_status {
+        ClientFpicIdsStatus::Granted => (true, None),
+        ClientFpicIdsStatus::NotObtained => (
+            false,
+            Some("consent never requested; canonical form only records not granted"),
+        ),
+        ClientFpicIdsStatus::Pending => (
+            false,
+            Some("consent decision pending; canonical form only records not granted"),
+        ),
+        ClientFpicIdsStatus::Revoked => (
+            false,
+            Some("consent revoked; canonical form only records not granted"),
+        ),
+        ClientFpicIdsStatus::Conditional(_) => (
+            false,
+            Some("conditional consent has no canonical form; treated as not granted"),
+        ),
+    };
+    context.fpic = FpicIdsState {
+        fpic_granted: granted,
+        ..context.fpic
+    };
+    if let Some(reason) = note {
+        unmapped.push(UnmappedField::new(
+            "fpic_ids_status",
+            &legacy.fpic_ids_status,
+            reason,
+        ));
+    }
+
+    let mut others = Vec::new();
+    for j in &legacy.jurisdictions {
+        if context.jurisdiction_profile_id.is_none() && registry.jurisdiction(j).is_some() {
+            context.jurisdiction_profile_id = Some(j.clone());
+        } else {
+            others.push(j.clone());
+        }
+    }
+    if !others.is_empty() {
+        unmapped.push(UnmappedField::new(
+            "jurisdictions",
+            &others,
+            "no loaded jurisdiction profile for these entries",
+        ));
+    }
+    unmapped.push(UnmappedField::new(
+        "last_updated",
+        &legacy.last_updated,
+        "context update time is not an FPIC decision time",
+    ));
+    if let Some(notes) = &legacy.notes {
+        unmapped.push(UnmappedField::new(
+            "notes",
+            notes,
+            "canonical context has no notes",
+        ));
+    }
+
+    Ok(Converted { context, unmapped })
+}
+
+/// Convert the camelCase `eco-corridor-context.v1` shape.
+pub fn from_v1(
+    legacy: &CorridorContextV1,
+    registry: &CorridorRegistry,
+) -> Result<Converted, CompatError> {
+    if legacy.version != CORRIDOR_CONTEXT_SCHEMA_V1 {
+        return Err(CompatError::UnknownVersion(legacy.version.clone()));
+    }
+    let profile = resolve(registry, &legacy.corridor_id)?;
+    let mut context = base_context(profile);
+    let mut unmapped = Vec::new();
+
+    unmapped.push(UnmappedField::new(
+        "ecoImpact",
+        &legacy.eco_impact,
+        NO_ABSOLUTE_DELTAS,
+    ));
+
+    if let Some(consent) = &legacy.fpic_ids_state {
+        if consent.subject_corridor_id != legacy.corridor_id {
+            return Err(CompatError::ConsentSubjectMismatch {
+                subject: consent.subject_corridor_id.clone(),
+                corridor: legacy.corridor_id.clone(),
+            });
+        }
+        let decided_at = match consent.status {
+            ConsentStatusV1::Revoked => consent.revoked_at.as_ref().unwrap_or(&consent.issued_at),
+            _ => &consent.issued_at,
+        };
+        context.fpic = FpicIdsState {
+            fpic_granted: consent.status == ConsentStatusV1::Granted,
+            last_decision_utc: decided_at.clone(),
+            ..context.fpic
+        };
+        unmapped.push(UnmappedField::new(
+            "fpicIdsState.issuerDid",
+            &consent.issuer_did,
+            "canonical FPIC state does not record the issuer",
+        ));
+    }
+
+    if !legacy.neurorights_capsule.flags.is_empty() {
+        unmapped.push(UnmappedField::new(
+            "neurorightsCapsule.flags",
+            &legacy.neurorights_capsule.flags,
+            "flags are not HGO fields; the corridor profile's HGO capsule applies",
+        ));
+    }
+
+    Ok(Converted { context, unmapped })
+}
+
+/// Decode a canonical context, tagged or serialized before the version tag
+/// existed. Missing guardrails come from the corridor's loaded profile, or
+/// are [`EcoGuardrails::strict`] when none is loaded.
+fn from_canonical(json: &Value, registry: &CorridorRegistry) -> Result<Converted, CompatError> {
+    let mut context: EcoCorridorContext = serde_json::from_value(json.clone())?;
+    context.version = CORRIDOR_CONTEXT_SCHEMA.to_string();
+    if json.get("eco_guardrails").is_none() {
+        context.eco_guardrails = registry
+            .latest(&context.corridor_id.code)
+            .map(|p| p.eco_guardrails.clone())
+            .unwrap_or_else(EcoGuardrails::strict);
+    }
+    Ok(Converted {
+        context,
+        unmapped: Vec::new(),
+    })
+}
+
+/// Decode any known corridor context shape, dispatching on the `version`
+/// tag where present and on field layout for the untagged legacy shapes.
+pub fn decode_corridor_context(
+    json: &Value,
+    registry: &CorridorRegistry,
+) -> Result<Converted, CompatError> {
+    let object = json.as_object().ok_or(CompatError::UnknownShape)?;
+    match object.get("version").and_then(Value::as_str) {
+        Some(CORRIDOR_CONTEXT_SCHEMA_V1) => {
+            from_v1(&serde_json::from_value(json.clone())?, registry)
+        }
+        Some(CORRIDOR_CONTEXT_SCHEMA) => from_canonical(json, registry),
+        Some(other) => Err(CompatError::UnknownVersion(other.to_string())),
+        None if object.contains_key("corridor_name") => {
+            from_client(&serde_json::from_value(json.clone())?, registry)
+        }
+        None if object.contains_key("strength") => from_canonical(json, registry),
+        None => Err(CompatError::UnknownShape),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::path::Path;
+
+    fn registry() -> CorridorRegistry {
+        CorridorRegistry::load_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("../qpudatashards"))
+            .unwrap()
+    }
+
+    #[test]
+    fn converts_client_shape_and_reports_scores() {
+        let legacy = json!({
+            "corridor_id": "tribal.gric-epa-2024",
+            "corridor_name": "GRIC",
+            "eco_impact": {
+                "climate_impact": 0.1, "biodiversity_impact": 0.1,
+                "biosphere_fragility": 0.1, "corridor_safety": 0.9, "service_impact": 0.1
+            },
+            "fpic_ids_status": { "Conditional": ["seasonal-only"] },
+            "jurisdictions": ["state.co-neuraldata-2024", "US/Arizona"],
+            "last_updated": "2024-05-01T00:00:00Z",
+            "notes": null
+        });
+        let converted = decode_corridor_context(&legacy, &registry()).unwrap();
+        let ctx = &converted.context;
+        assert_eq!(ctx.corridor_id.code, "tribal.gric-epa-2024");
+        assert_eq!(ctx.display_name.as_deref(), Some("GRIC"));
+        assert!(!ctx.fpic.fpic_granted);
+        assert!(ctx.eco.is_none());
+        assert_eq!(
+            ctx.jurisdiction_profile_id.as_deref(),
+            Some("state.co-neuraldata-2024")
+        );
+        let fields: Vec<_> = converted
+            .unmapped
+            .iter()
+            .map(|u| u.field.as_str())
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                "eco_impact",
+                "fpic_ids_status",
+                "jurisdictions",
+                "last_updated"
+            ]
+        );
+        assert_eq!(converted.unmapped[2].value, json!(["US/Arizona"]));
+    }
+
+    #[test]
+    fn converts_v1_shape() {
+        let legacy = json!({
+            "version": "eco-corridor-context.v1",
+            "corridorId": "city.phx-smartinfra-2024",
+            "ecoImpact": {
+                "soilHealth": 0.8, "waterQuality": 0.7,
+                "microbiomeDiversity": 0.6, "corridorResilience": 0.9
+            },
+            "fpicIdsState": {
+                "issuerDid": "did:example:council",
+                "subjectCorridorId": "city.phx-smartinfra-2024",
+                "status": "Revoked",
+                "issuedAt": "2024-01-01T00:00:00Z",
+                "revokedAt": "2024-02-01T00:00:00Z"
+            },
+            "neurorightsCapsule": { "flags": [] }
+        });
+        let converted = decode_corridor_context(&legacy, &registry()).unwrap();
+        assert!(!converted.context.fpic.fpic_granted);
+        assert_eq!(
+            converted.context.fpic.last_decision_utc,
+            "2024-02-01T00:00:00Z"
+        );
+        assert_eq!(converted.unmapped.len(), 2);
+    }
+
+    #[test]
+    fn canonical_round_trips_losslessly() {
+        let reg = registry();
+        let ctx = base_context(reg.latest("tribal.gric-epa-2024").unwrap());
+        let converted =
+            decode_corridor_context(&serde_json::to_value(&ctx).unwrap(), &reg).unwrap();
+        assert!(converted.is_lossless());
+        assert_eq!(converted.context.corridor_id, ctx.corridor_id);
+    }
+
+    /// A context as serialized by the baseline crate: no version tag, no
+    /// guardrails, required absolute eco deltas without lead.
+    fn baseline_context(code: &str) -> Value {
+        json!({
+            "corridor_id": { "tier": "Municipal", "code": code, "version": "2024.1" },
+            "strength": "AdvisoryOnly",
+            "fpic": {
+                "fpic_granted": false, "revocable": false,
+                "last_decision_utc": "2024-01-01T00:00:00Z", "community_veto_active": false
+            },
+            "neurorights": {
+                "inner_outer_enforced": false, "neural_data_safety_only": false,
+                "requires_opt_out_channels": false, "forbids_inner_for_access": false
+            },
+            "eco": {
+                "delta_emissions_co2e": 12.0, "delta_pm25": 0.5,
+                "delta_water_use_m3": 3.0, "delta_heat_index_c": 0.1
+            },
+            "jurisdiction_profile_id": null
+        })
+    }
+
+    #[test]
+    fn migrates_baseline_canonical_guardrails() {
+        let reg = registry();
+        let converted =
+            decode_corridor_context(&baseline_context("city.phx-smartinfra-2024"), &reg).unwrap();
+        let ctx = &converted.context;
+        assert_eq!(ctx.version, CORRIDOR_CONTEXT_SCHEMA);
+        assert_eq!(
+            ctx.eco_guardrails,
+            reg.latest("city.phx-smartinfra-2024")
+                .unwrap()
+                .eco_guardrails
+        );
+        let eco = ctx.eco.as_ref().unwrap();
+        assert_eq!(eco.delta_emissions_co2e, 12.0);
+        assert_eq!(eco.delta_lead_ppb, None);
+
+        let unknown =
+            decode_corridor_context(&baseline_context("city.nowhere-2024"), &reg).unwrap();
+        assert_eq!(unknown.context.eco_guardrails, EcoGuardrails::strict());
+
+        // A tagged context without guardrails follows the same rule.
+        let mut tagged = baseline_context("city.phx-smartinfra-2024");
+        tagged["version"] = json!(CORRIDOR_CONTEXT_SCHEMA);
+        let converted = decode_corridor_context(&tagged, &reg).unwrap();
+        assert_eq!(converted.context.eco_guardrails, ctx.eco_guardrails);
+    }
+
+    #[test]
+    fn reports_collapsed_client_fpic_status() {
+        let legacy = json!({
+            "corridor_id": "tribal.gric-epa-2024",
+            "corridor_name": "GRIC",
+            "eco_impact": {
+                "climate_impact": 0.1, "biodiversity_impact": 0.1,
+                "biosphere_fragility": 0.1, "corridor_safety": 0.9, "service_impact": 0.1
+            },
+            "fpic_ids_status": "Pending",
+            "jurisdictions": [],
+            "last_updated": "2024-05-01T00:00:00Z",
+            "notes": null
+        });
+        let converted = decode_corridor_context(&legacy, &registry()).unwrap();
+        assert!(!converted.context.fpic.fpic_granted);
+        let fpic = converted
+            .unmapped
+            .iter()
+            .find(|u| u.field == "fpic_ids_status")
+            .unwrap();
+        assert_eq!(fpic.value, json!("Pending"));
+    }
+
+    #[test]
+    fn client_gate_needs_consent_and_admissible_impact() {
+        let mut ctx = ClientCorridorContext::new("gric".into(), "GRIC".into());
+        ctx.jurisdictions.push("US/Arizona".into());
+        ctx.eco_impact.corridor_safety = 0.9;
+        assert!(!ctx.is_operational());
+        ctx.fpic_ids_status = ClientFpicIdsStatus::Conditional(vec!["operations=audit".into()]);
+        assert!(ctx.validate().is_ok());
+        ctx.eco_impact.climate_impact = 0.5;
+        assert!(!ctx.is_operational());
+    }
+
+    #[test]
+    fn rejects_unknown_version_and_corridor() {
+        let reg = registry();
+        let future = json!({ "version": "eco-corridor-context.v9" });
+        assert!(matches!(
+            decode_corridor_context(&future, &reg),
+            Err(CompatError::UnknownVersion(_))
+        ));
+        let legacy = ClientCorridorContext {
+            corridor_id: "phx_001".into(),
+            corridor_name: "Phoenix".into(),
+            eco_impact: ClientEcoImpactMetrics {
+                climate_impact: 0.0,
+                biodiversity_impact: 0.0,
+                biosphere_fragility: 0.0,
+                corridor_safety: 1.0,
+                service_impact: 0.0,
+            },
+            fpic_ids_status: ClientFpicIdsStatus::Granted,
+            jurisdictions: vec![],
+            last_updated: String::new(),
+            notes: None,
+        };
+        assert!(matches!(
+            from_client(&legacy, &reg),
+            Err(CompatError::UnknownCorridor(_))
+        ));
+    }
+}
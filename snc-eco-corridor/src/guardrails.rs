@@ -0,0 +1,227 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{CorridorId, CorridorStrength, EcoCorridorContext, EcoImpactMetrics};
+
+/// Eco guardrail dimensions declared in a corridor's `eco_guardrails` section.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum GuardrailKind {
+    Emissions,
+    Pm25,
+    Lead,
+    WaterUse,
+    HeatIndex,
+}
+
+/// One guardrail the proposed operation would exceed.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct GuardrailBreach {
+    pub guardrail: GuardrailKind,
+    pub limit: f64,
+    /// `None` when the operation did not report this delta.
+    pub proposed: Option<f64>,
+    /// `proposed - limit`; infinite when the delta is missing or not a number.
+    pub excess: f64,
+}
+
+/// Outcome of gating one operation against its corridor.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OperationDecision {
+    /// No breach, FPIC satisfied, no active veto.
+    Permitted,
+    /// StrongGuard corridor: actuation only after mitigation brings the
+    /// operation back within guardrails and FPIC is satisfied.
+    MitigationRequired,
+    /// HardVeto corridor: any breach, missing FPIC or community veto blocks.
+    Blocked,
+    /// AdvisoryOnly corridor: informative only, never authorizes actuation.
+    Advisory,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OperationVerdict {
+    pub corridor_id: CorridorId,
+    pub strength: CorridorStrength,
+    pub decision: OperationDecision,
+    pub breaches: Vec<GuardrailBreach>,
+    pub fpic_granted: bool,
+    /// Whether an active community veto covers this operation's class.
+    pub community_veto_active: bool,
+}
+
+impl OperationVerdict {
+    /// Only an explicit `Permitted` decision may drive actuation.
+    pub fn authorizes_actuation(&self) -> bool {
+        self.decision == OperationDecision::Permitted
+    }
+}
+
+/// Compare the proposed eco deltas with the corridor guardrails and apply
+/// the corridor strength semantics. Missing and non-finite deltas count as
+/// breaches; guardrails the corridor does not declare are not checked. The
+/// community veto applies when its scope covers `operation`, an operation
+/// class such as `eco-actuation`.
+pub fn evaluate_operation(
+    ctx: &EcoCorridorContext,
+    operation: &str,
+    proposed_metrics: &EcoImpactMetrics,
+) -> OperationVerdict {
+    let limits = &ctx.eco_guardrails;
+    let checks = [
+        (
+            GuardrailKind::Emissions,
+            limits.max_delta_emissions_co2e,
+            Some(proposed_metrics.delta_emissions_co2e),
+        ),
+        (
+            GuardrailKind::Pm25,
+            Some(limits.max_delta_pm25_ug_m3),
+            Some(proposed_metrics.delta_pm25),
+        ),
+        (
+            GuardrailKind::Lead,
+            Some(limits.max_delta_lead_ppb),
+            proposed_metrics.delta_lead_ppb,
+        ),
+        (
+            GuardrailKind::WaterUse,
+            Some(limits.max_delta_water_use_m3),
+            Some(proposed_metrics.delta_water_use_m3),
+        ),
+        (
+            GuardrailKind::HeatIndex,
+            Some(limits.max_delta_heat_index_c),
+            Some(proposed_metrics.delta_heat_index_c),
+        ),
+    ];
+    let breaches: Vec<GuardrailBreach> = checks
+        .into_iter()
+        .filter_map(|(guardrail, limit, proposed)| {
+            let limit = limit?;
+            let excess = match proposed {
+                Some(p) if p.is_nan() => f64::INFINITY,
+                Some(p) if p > limit => p - limit,
+                Some(_) => return None,
+                None => f64::INFINITY,
+            };
+            Some(GuardrailBreach {
+                guardrail,
+                limit,
+                proposed,
+                excess,
+            })
+        })
+        .collect();
+
+    let fpic_granted = ctx.fpic.fpic_granted;
+    let community_veto_active = ctx.fpic.vetoes(operation);
+    let clear = breaches.is_empty() && fpic_granted && !community_veto_active;
+
+    let decision = match ctx.strength {
+        CorridorStrength::AdvisoryOnly => OperationDecision::Advisory,
+        _ if clear => OperationDecision::Permitted,
+        CorridorStrength::HardVeto => OperationDecision::Blocked,
+        CorridorStrength::StrongGuard => OperationDecision::MitigationRequired,
+    };
+
+    OperationVerdict {
+        corridor_id: ctx.corridor_id.clone(),
+        strength: ctx.strength.clone(),
+        decision,
+        breaches,
+        fpic_granted,
+        community_veto_active,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CorridorProfile;
+
+    const GRIC: &str = include_str!("../../qpudatashards/corridors/tribal.gric-epa-2024.aln");
+    const PHX: &str = include_str!("../../qpudatashards/corridors/city.phx-smartinfra-2024.aln");
+    const ACTUATION: &str = "eco-actuation";
+
+    fn metrics(pm25: f64) -> EcoImpactMetrics {
+        EcoImpactMetrics {
+            delta_emissions_co2e: 0.0,
+            delta_pm25: pm25,
+            delta_lead_ppb: Some(0.0),
+            delta_water_use_m3: 0.0,
+            delta_heat_index_c: 0.0,
+        }
+    }
+
+    fn granted_ctx(shard: &str) -> EcoCorridorContext {
+        let mut ctx = CorridorProfile::parse(shard).unwrap().context(metrics(0.0));
+        ctx.fpic.fpic_granted = true;
+        ctx
+    }
+
+    #[test]
+    fn hard_veto_blocks_any_breach() {
+        let ctx = granted_ctx(GRIC);
+        let verdict = evaluate_operation(&ctx, ACTUATION, &metrics(0.1));
+        assert_eq!(verdict.decision, OperationDecision::Blocked);
+        assert_eq!(verdict.breaches.len(), 1);
+        assert_eq!(verdict.breaches[0].guardrail, GuardrailKind::Pm25);
+        assert!(evaluate_operation(&ctx, ACTUATION, &metrics(0.0)).authorizes_actuation());
+    }
+
+    #[test]
+    fn hard_veto_blocks_on_community_veto() {
+        let mut ctx = granted_ctx(GRIC);
+        ctx.fpic.community_veto_active = true;
+        let verdict = evaluate_operation(&ctx, ACTUATION, &metrics(0.0));
+        assert_eq!(verdict.decision, OperationDecision::Blocked);
+        assert!(verdict.breaches.is_empty());
+
+        // GRIC's veto does not cover this class.
+        let verdict = evaluate_operation(&ctx, "data-export", &metrics(0.0));
+        assert!(!verdict.community_veto_active);
+        assert!(verdict.authorizes_actuation());
+
+        // Contexts without a recorded scope are vetoed for every class.
+        ctx.fpic.community_veto_scope = None;
+        let verdict = evaluate_operation(&ctx, "data-export", &metrics(0.0));
+        assert_eq!(verdict.decision, OperationDecision::Blocked);
+    }
+
+    #[test]
+    fn missing_lead_and_emissions_are_checked() {
+        let mut ctx = granted_ctx(GRIC);
+        let mut unmeasured = metrics(0.0);
+        unmeasured.delta_lead_ppb = None;
+        let verdict = evaluate_operation(&ctx, ACTUATION, &unmeasured);
+        assert_eq!(verdict.decision, OperationDecision::Blocked);
+        assert_eq!(verdict.breaches[0].guardrail, GuardrailKind::Lead);
+        assert_eq!(verdict.breaches[0].proposed, None);
+
+        // GRIC declares no CO2e ceiling, so emissions are not gated.
+        let mut emitting = metrics(0.0);
+        emitting.delta_emissions_co2e = 2.5;
+        assert!(evaluate_operation(&ctx, ACTUATION, &emitting).authorizes_actuation());
+
+        ctx.eco_guardrails.max_delta_emissions_co2e = Some(0.0);
+        let verdict = evaluate_operation(&ctx, ACTUATION, &emitting);
+        assert_eq!(verdict.breaches[0].guardrail, GuardrailKind::Emissions);
+        assert_eq!(verdict.breaches[0].excess, 2.5);
+    }
+
+    #[test]
+    fn strong_guard_requires_mitigation() {
+        let mut ctx = granted_ctx(GRIC);
+        ctx.strength = CorridorStrength::StrongGuard;
+        let verdict = evaluate_operation(&ctx, ACTUATION, &metrics(f64::NAN));
+        assert_eq!(verdict.decision, OperationDecision::MitigationRequired);
+        assert!(verdict.breaches[0].excess.is_infinite());
+    }
+
+    #[test]
+    fn advisory_never_authorizes() {
+        let ctx = granted_ctx(PHX);
+        let verdict = evaluate_operation(&ctx, ACTUATION, &metrics(0.0));
+        assert_eq!(verdict.decision, OperationDecision::Advisory);
+        assert!(!verdict.authorizes_actuation());
+    }
+}
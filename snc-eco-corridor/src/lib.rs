@@ -1,7 +1,15 @@
 use serde::{Deserialize, Serialize};
 
+pub mod compat;
+pub mod guardrails;
+pub mod loader;
+
+pub use compat::{decode_corridor_context, CompatError, Converted, UnmappedField};
+pub use guardrails::{evaluate_operation, GuardrailBreach, OperationDecision, OperationVerdict};
+pub use loader::{CorridorLoadError, CorridorProfile, CorridorRegistry, JurisdictionProfile};
+
 /// Coarse legal tier for a corridor.
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum CorridorTier {
     Tribal,
     Municipal,
@@ -15,9 +23,9 @@ pub enum CorridorTier {
 /// Trust / enforcement strength of the corridor.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum CorridorStrength {
-    HardVeto,      // Binding FPIC + community veto, actuation blocked on deny.
-    StrongGuard,   // Enforceable obligations, but weaker FPIC.
-    AdvisoryOnly,  // Informative; cannot by itself permit actuation.
+    HardVeto,     // Binding FPIC + community veto, actuation blocked on deny.
+    StrongGuard,  // Enforceable obligations, but weaker FPIC.
+    AdvisoryOnly, // Informative; cannot by itself permit actuation.
 }
 
 /// Canonical corridor identifier: jurisdictional + semantic name + version.
@@ -38,6 +46,22 @@ pub struct FpicIdsState {
     pub revocable: bool,
     pub last_decision_utc: String,
     pub community_veto_active: bool,
+    /// Operation classes the community veto covers. `None` for contexts
+    /// serialized before scopes were recorded: the veto then covers every
+    /// operation.
+    #[serde(default)]
+    pub community_veto_scope: Option<Vec<String>>,
+}
+
+impl FpicIdsState {
+    /// Whether an active community veto covers `operation`.
+    pub fn vetoes(&self, operation: &str) -> bool {
+        self.community_veto_active
+            && self
+                .community_veto_scope
+                .as_ref()
+                .is_none_or(|scope| scope.iter().any(|class| class == operation))
+    }
 }
 
 /// Neurorights / mental-privacy capsule (HGO) attached to the corridor.
@@ -58,18 +82,70 @@ pub struct NeurorightsCapsuleHgo {
 pub struct EcoImpactMetrics {
     pub delta_emissions_co2e: f64,
     pub delta_pm25: f64,
+    /// `None` when the producer did not measure lead; guardrail checks treat
+    /// that as a breach rather than as zero.
+    pub delta_lead_ppb: Option<f64>,
     pub delta_water_use_m3: f64,
     pub delta_heat_index_c: f64,
 }
 
+/// Per-operation eco ceilings declared by a corridor's `eco_guardrails` shard section.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct EcoGuardrails {
+    /// `None` when the corridor declares no CO2e ceiling; emissions are then
+    /// not gated.
+    #[serde(default)]
+    pub max_delta_emissions_co2e: Option<f64>,
+    pub max_delta_pm25_ug_m3: f64,
+    pub max_delta_lead_ppb: f64,
+    pub max_delta_water_use_m3: f64,
+    pub max_delta_heat_index_c: f64,
+}
+
+impl EcoGuardrails {
+    /// Zero ceilings: any positive delta is a breach. Used for contexts that
+    /// predate guardrails, so they fail closed.
+    pub fn strict() -> Self {
+        Self {
+            max_delta_emissions_co2e: Some(0.0),
+            max_delta_pm25_ug_m3: 0.0,
+            max_delta_lead_ppb: 0.0,
+            max_delta_water_use_m3: 0.0,
+            max_delta_heat_index_c: 0.0,
+        }
+    }
+}
+
+/// Schema tag of the canonical corridor context on the wire.
+/// `eco-corridor-context.v1` is the camelCase Kotlin/JSON shape handled by [`compat`].
+pub const CORRIDOR_CONTEXT_SCHEMA: &str = "eco-corridor-context.v2";
+
+fn corridor_context_schema() -> String {
+    CORRIDOR_CONTEXT_SCHEMA.to_string()
+}
+
 /// Fully bound corridor context passed into every SNC.
+/// This is the canonical corridor model; legacy shapes convert into it via [`compat`].
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EcoCorridorContext {
+    #[serde(default = "corridor_context_schema")]
+    pub version: String,
     pub corridor_id: CorridorId,
+    /// Human-readable corridor name, if the producer supplied one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
     pub strength: CorridorStrength,
     pub fpic: FpicIdsState,
     pub neurorights: NeurorightsCapsuleHgo,
-    pub eco: EcoImpactMetrics,
+    /// Measured absolute eco deltas; `None` when the producer only had
+    /// normalized scores, which do not convert to absolute deltas.
+    #[serde(default)]
+    pub eco: Option<EcoImpactMetrics>,
+    /// Ceilings that any proposed operation's eco deltas are checked against.
+    /// Contexts serialized without guardrails get [`EcoGuardrails::strict`];
+    /// [`decode_corridor_context`] fills them from the corridor's profile.
+    #[serde(default = "EcoGuardrails::strict")]
+    pub eco_guardrails: EcoGuardrails,
     /// Optional jurisdictional profile ID for neural data statutes etc.
     /// e.g. "state.co-neuraldata-2024", "state.ca-neurodata-2025".
     pub jurisdiction_profile_id: Option<String>,
@@ -0,0 +1,648 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    CorridorId, CorridorStrength, CorridorTier, EcoCorridorContext, EcoGuardrails,
+    EcoImpactMetrics, FpicIdsState, NeurorightsCapsuleHgo, CORRIDOR_CONTEXT_SCHEMA,
+};
+
+/// Shard format version understood by this loader.
+pub const SHARD_FORMAT_VERSION: &str = "1.0";
+/// `schema` line of a corridor profile shard (`qpudatashards/corridors/*.aln`).
+pub const CORRIDOR_PROFILE_SCHEMA: &str = "viva.corridor.profile";
+/// `schema` line of a jurisdiction shard (`qpudatashards/juris/*.aln`).
+pub const JURISDICTION_NEURALDATA_SCHEMA: &str = "viva.jurisdiction.neuraldata";
+
+#[derive(Debug, Error)]
+pub enum CorridorLoadError {
+    #[error("failed to read shard {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("line {line}: {message}")]
+    Syntax { line: usize, message: String },
+    #[error("unsupported shard format version {0}")]
+    UnsupportedVersion(String),
+    #[error("expected schema {expected}, found {found}")]
+    SchemaMismatch { expected: String, found: String },
+    #[error("missing section {0}")]
+    MissingSection(String),
+    #[error("missing field {section}.{key}")]
+    MissingField { section: String, key: String },
+    #[error("invalid value for {section}.{key}: {value}")]
+    InvalidValue {
+        section: String,
+        key: String,
+        value: String,
+    },
+    #[error("proofhex check failed for {id}: {reason}")]
+    ProofHex { id: String, reason: String },
+    #[error("shard file {path} does not match declared id {id}")]
+    FileNameMismatch { path: PathBuf, id: String },
+    #[error("duplicate corridor {0:?}")]
+    DuplicateCorridor(CorridorId),
+    #[error("duplicate jurisdiction profile {0}")]
+    DuplicateJurisdiction(String),
+}
+
+/// FPIC / IDS obligations declared by the shard's `fpic_ids` section.
+/// This is policy, not a decision; see [`FpicRequirements::initial_state`].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FpicRequirements {
+    pub required: bool,
+    pub revocable: bool,
+    /// Operation classes the community veto applies to; empty for `none`.
+    pub community_veto_scope: Vec<String>,
+}
+
+impl FpicRequirements {
+    /// State before any FPIC decision has been recorded. Corridors that
+    /// require FPIC start ungranted; corridors that do not are treated as granted.
+    pub fn initial_state(&self) -> FpicIdsState {
+        FpicIdsState {
+            fpic_granted: !self.required,
+            revocable: self.revocable,
+            last_decision_utc: String::new(),
+            community_veto_active: false,
+            community_veto_scope: Some(self.community_veto_scope.clone()),
+        }
+    }
+}
+
+/// A corridor profile shard, fully typed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CorridorProfile {
+    pub id: CorridorId,
+    pub description: String,
+    pub strength: CorridorStrength,
+    pub fpic: FpicRequirements,
+    pub neurorights: NeurorightsCapsuleHgo,
+    pub eco_guardrails: EcoGuardrails,
+    pub proofhex: String,
+}
+
+impl CorridorProfile {
+    pub fn parse(input: &str) -> Result<Self, CorridorLoadError> {
+        let shard = RawShard::parse(input)?;
+        shard.expect_schema(CORRIDOR_PROFILE_SCHEMA)?;
+
+        let corridor = shard.section("corridor")?;
+        let code = corridor.required("id")?.to_string();
+        let id = CorridorId {
+            tier: corridor.parsed("tier", parse_tier)?,
+            code,
+            version: corridor.required("version")?.to_string(),
+        };
+        let strength = corridor.parsed("strength", parse_strength)?;
+        let description = corridor
+            .optional("description")
+            .unwrap_or_default()
+            .to_string();
+
+        let fpic_ids = shard.section("fpic_ids")?;
+        let fpic = FpicRequirements {
+            required: fpic_ids.flag("required")?,
+            revocable: fpic_ids.flag("revocable")?,
+            community_veto_scope: parse_list(fpic_ids.required("community_veto_scope")?),
+        };
+
+        let hgo = shard.section("neurorights_hgo")?;
+        let neurorights = NeurorightsCapsuleHgo {
+            inner_outer_enforced: hgo.flag("inner_outer_enforced")?,
+            neural_data_safety_only: hgo.flag("neural_data_safety_only")?,
+            requires_opt_out_channels: hgo.flag("requires_opt_out_channels")?,
+            forbids_inner_for_access: hgo.flag("forbids_inner_for_access")?,
+        };
+
+        let guardrails = shard.section("eco_guardrails")?;
+        let eco_guardrails = EcoGuardrails {
+            max_delta_emissions_co2e: guardrails.optional_limit("max_delta_emissions_co2e")?,
+            max_delta_pm25_ug_m3: guardrails.limit("max_delta_pm25_ug_m3")?,
+            max_delta_lead_ppb: guardrails.limit("max_delta_lead_ppb")?,
+            max_delta_water_use_m3: guardrails.limit("max_delta_water_use_m3")?,
+            max_delta_heat_index_c: guardrails.limit("max_delta_heat_index_c")?,
+        };
+
+        let proofhex = shard.verified_proofhex("CORRIDOR", &id.code)?;
+
+        Ok(Self {
+            id,
+            description,
+            strength,
+            fpic,
+            neurorights,
+            eco_guardrails,
+            proofhex,
+        })
+    }
+
+    /// Bind this profile into the context passed to every SNC, starting from
+    /// the corridor's initial FPIC state.
+    pub fn context(&self, eco: EcoImpactMetrics) -> EcoCorridorContext {
+        EcoCorridorContext {
+            version: CORRIDOR_CONTEXT_SCHEMA.to_string(),
+            corridor_id: self.id.clone(),
+            display_name: None,
+            strength: self.strength.clone(),
+            fpic: self.fpic.initial_state(),
+            neurorights: self.neurorights.clone(),
+            eco: Some(eco),
+            eco_guardrails: self.eco_guardrails.clone(),
+            jurisdiction_profile_id: None,
+        }
+    }
+}
+
+/// A jurisdictional neural-data statute profile (e.g. `state.co-neuraldata-2024`).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JurisdictionProfile {
+    pub id: String,
+    pub scope: String,
+    pub description: String,
+    pub constraints: BTreeMap<String, bool>,
+    pub applies_to_planes: Vec<String>,
+    pub host_local_default: bool,
+    pub export_requires_neuraldata_profile: bool,
+    pub proofhex: String,
+}
+
+impl JurisdictionProfile {
+    pub fn parse(input: &str) -> Result<Self, CorridorLoadError> {
+        let shard = RawShard::parse(input)?;
+        shard.expect_schema(JURISDICTION_NEURALDATA_SCHEMA)?;
+
+        let profile = shard.section("profile")?;
+        let id = profile.required("id")?.to_string();
+        let scope = profile.required("scope")?.to_string();
+        let description = profile
+            .optional("description")
+            .unwrap_or_default()
+            .to_string();
+
+        let section = shard.section("constraints")?;
+        let mut constraints = BTreeMap::new();
+        for (key, _) in &section.entries {
+            constraints.insert(key.clone(), section.flag(key)?);
+        }
+
+        let link = shard.section("link")?;
+        let applies_to_planes = parse_list(link.required("applies_to_planes")?);
+        let host_local_default = link.flag("host_local_default")?;
+        let export_requires_neuraldata_profile = link.flag("export_requires_neuraldata_profile")?;
+
+        let proofhex = shard.verified_proofhex("JURIS", &id)?;
+
+        Ok(Self {
+            id,
+            scope,
+            description,
+            constraints,
+            applies_to_planes,
+            host_local_default,
+            export_requires_neuraldata_profile,
+            proofhex,
+        })
+    }
+}
+
+/// Registry of loaded corridor and jurisdiction shards, keyed by
+/// `CorridorId` (tier + code + version) and jurisdiction id respectively.
+#[derive(Clone, Debug, Default)]
+pub struct CorridorRegistry {
+    corridors: HashMap<CorridorId, CorridorProfile>,
+    jurisdictions: HashMap<String, JurisdictionProfile>,
+}
+
+impl CorridorRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load every `*.aln` shard under `root` (e.g. `qpudatashards/`), one
+    /// directory level deep. Shards are dispatched on their `schema` line;
+    /// other schemas are ignored. The file stem must equal the declared id.
+    pub fn load_dir(root: impl AsRef<Path>) -> Result<Self, CorridorLoadError> {
+        let mut registry = Self::new();
+        let mut paths = Vec::new();
+        collect_aln_files(root.as_ref(), 1, &mut paths)?;
+        paths.sort();
+        for path in paths {
+            registry.load_file(&path)?;
+        }
+        Ok(registry)
+    }
+
+    /// Load a single shard file into the registry.
+    pub fn load_file(&mut self, path: &Path) -> Result<(), CorridorLoadError> {
+        let input = fs::read_to_string(path).map_err(|source| CorridorLoadError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let stem = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default();
+        let check_stem = |id: &str| {
+            if stem == id {
+                Ok(())
+            } else {
+                Err(CorridorLoadError::FileNameMismatch {
+                    path: path.to_path_buf(),
+                    id: id.to_string(),
+                })
+            }
+        };
+
+        match RawShard::parse(&input)?.schema.as_deref() {
+            Some(CORRIDOR_PROFILE_SCHEMA) => {
+                let profile = CorridorProfile::parse(&input)?;
+                check_stem(&profile.id.code)?;
+                self.insert_corridor(profile)
+            }
+            Some(JURISDICTION_NEURALDATA_SCHEMA) => {
+                let profile = JurisdictionProfile::parse(&input)?;
+                check_stem(&profile.id)?;
+                self.insert_jurisdiction(profile)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn insert_corridor(&mut self, profile: CorridorProfile) -> Result<(), CorridorLoadError> {
+        if self.corridors.contains_key(&profile.id) {
+            return Err(CorridorLoadError::DuplicateCorridor(profile.id));
+        }
+        self.corridors.insert(profile.id.clone(), profile);
+        Ok(())
+    }
+
+    pub fn insert_jurisdiction(
+        &mut self,
+        profile: JurisdictionProfile,
+    ) -> Result<(), CorridorLoadError> {
+        if self.jurisdictions.contains_key(&profile.id) {
+            return Err(CorridorLoadError::DuplicateJurisdiction(profile.id));
+        }
+        self.jurisdictions.insert(profile.id.clone(), profile);
+        Ok(())
+    }
+
+    pub fn get(&self, id: &CorridorId) -> Option<&CorridorProfile> {
+        self.corridors.get(id)
+    }
+
+    /// Highest loaded version of the corridor with the given code.
+    pub fn latest(&self, code: &str) -> Option<&CorridorProfile> {
+        self.corridors
+            .values()
+            .filter(|p| p.id.code == code)
+            .max_by(|a, b| compare_versions(&a.id.version, &b.id.version))
+    }
+
+    pub fn jurisdiction(&self, id: &str) -> Option<&JurisdictionProfile> {
+        self.jurisdictions.get(id)
+    }
+
+    pub fn corridors(&self) -> impl Iterator<Item = &CorridorProfile> {
+        self.corridors.values()
+    }
+
+    pub fn jurisdictions(&self) -> impl Iterator<Item = &JurisdictionProfile> {
+        self.jurisdictions.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.corridors.len() + self.jurisdictions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+fn collect_aln_files(
+    dir: &Path,
+    depth: usize,
+    out: &mut Vec<PathBuf>,
+) -> Result<(), CorridorLoadError> {
+    let io_err = |source| CorridorLoadError::Io {
+        path: dir.to_path_buf(),
+        source,
+    };
+    for entry in fs::read_dir(dir).map_err(io_err)? {
+        let path = entry.map_err(io_err)?.path();
+        if path.is_dir() {
+            if depth > 0 {
+                collect_aln_files(&path, depth - 1, out)?;
+            }
+        } else if path.extension().and_then(|e| e.to_str()) == Some("aln") {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Compare dotted numeric versions such as `2024.1` and `2024.10`.
+fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    let parts = |v: &str| -> Vec<u64> { v.split('.').map(|p| p.parse().unwrap_or(0)).collect() };
+    parts(a).cmp(&parts(b))
+}
+
+fn parse_tier(value: &str) -> Option<CorridorTier> {
+    match value {
+        "Tribal" => Some(CorridorTier::Tribal),
+        "Municipal" => Some(CorridorTier::Municipal),
+        "County" => Some(CorridorTier::County),
+        "State" => Some(CorridorTier::State),
+        "Federal" => Some(CorridorTier::Federal),
+        "International" => Some(CorridorTier::International),
+        "InternalDoctrine" => Some(CorridorTier::InternalDoctrine),
+        _ => None,
+    }
+}
+
+fn parse_strength(value: &str) -> Option<CorridorStrength> {
+    match value {
+        "HardVeto" => Some(CorridorStrength::HardVeto),
+        "StrongGuard" => Some(CorridorStrength::StrongGuard),
+        "AdvisoryOnly" => Some(CorridorStrength::AdvisoryOnly),
+        _ => None,
+    }
+}
+
+/// Comma-separated list; the literal `none` is the empty list.
+fn parse_list(value: &str) -> Vec<String> {
+    if value == "none" {
+        return Vec::new();
+    }
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Untyped view of a `viva.*` shard: top-level `version`/`schema` lines,
+/// named sections of indented `key value` entries, and a trailing `proofhex`.
+struct RawShard {
+    version: Option<String>,
+    schema: Option<String>,
+    sections: Vec<RawSection>,
+    proofhex: Option<String>,
+}
+
+struct RawSection {
+    name: String,
+    entries: Vec<(String, String)>,
+}
+
+impl RawShard {
+    fn parse(input: &str) -> Result<Self, CorridorLoadError> {
+        let mut shard = RawShard {
+            version: None,
+            schema: None,
+            sections: Vec::new(),
+            proofhex: None,
+        };
+        for (idx, line) in input.lines().enumerate() {
+            let line_no = idx + 1;
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let syntax = |message: &str| CorridorLoadError::Syntax {
+                line: line_no,
+                message: message.to_string(),
+            };
+            // The proofhex seal covers everything before it.
+            if shard.proofhex.is_some() {
+                return Err(syntax("content after proofhex seal"));
+            }
+            let (key, value) = match trimmed.split_once(char::is_whitespace) {
+                Some((k, v)) => (k, unquote(v.trim())),
+                None => (trimmed, String::new()),
+            };
+            let indented = line.starts_with(char::is_whitespace);
+            if indented {
+                let section = shard
+                    .sections
+                    .last_mut()
+                    .ok_or_else(|| syntax("entry outside of a section"))?;
+                if section.entries.iter().any(|(k, _)| k == key) {
+                    return Err(syntax("duplicate key"));
+                }
+                section.entries.push((key.to_string(), value));
+                continue;
+            }
+            match (key, value.is_empty()) {
+                ("version", false) => shard.version = Some(value),
+                ("schema", false) => shard.schema = Some(value),
+                ("proofhex", false) => shard.proofhex = Some(value),
+                (name, true) => {
+                    if shard.sections.iter().any(|s| s.name == name) {
+                        return Err(syntax("duplicate section"));
+                    }
+                    shard.sections.push(RawSection {
+                        name: name.to_string(),
+                        entries: Vec::new(),
+                    });
+                }
+                _ => return Err(syntax("unknown top-level field")),
+            }
+        }
+        Ok(shard)
+    }
+
+    fn expect_schema(&self, expected: &str) -> Result<(), CorridorLoadError> {
+        match self.version.as_deref() {
+            Some(SHARD_FORMAT_VERSION) => {}
+            other => {
+                return Err(CorridorLoadError::UnsupportedVersion(
+                    other.unwrap_or("<missing>").to_string(),
+                ))
+            }
+        }
+        match self.schema.as_deref() {
+            Some(found) if found == expected => Ok(()),
+            other => Err(CorridorLoadError::SchemaMismatch {
+                expected: expected.to_string(),
+                found: other.unwrap_or("<missing>").to_string(),
+            }),
+        }
+    }
+
+    fn section(&self, name: &str) -> Result<&RawSection, CorridorLoadError> {
+        self.sections
+            .iter()
+            .find(|s| s.name == name)
+            .ok_or_else(|| CorridorLoadError::MissingSection(name.to_string()))
+    }
+
+    /// Check the integrity marker: `0x<KIND>-<TAG>`, uppercase, where `TAG`
+    /// ends with the same release segment (e.g. `2024`) as the declared id.
+    fn verified_proofhex(&self, kind: &str, id: &str) -> Result<String, CorridorLoadError> {
+        let fail = |reason: &str| CorridorLoadError::ProofHex {
+            id: id.to_string(),
+            reason: reason.to_string(),
+        };
+        let marker = self
+            .proofhex
+            .as_deref()
+            .ok_or_else(|| fail("missing proofhex"))?;
+        let body = marker
+            .strip_prefix("0x")
+            .ok_or_else(|| fail("marker must start with 0x"))?;
+        if !body
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '-')
+        {
+            return Err(fail(
+                "marker must be uppercase alphanumeric with '-' separators",
+            ));
+        }
+        let tag = body
+            .strip_prefix(kind)
+            .and_then(|rest| rest.strip_prefix('-'))
+            .filter(|tag| !tag.is_empty())
+            .ok_or_else(|| fail(&format!("marker kind must be {kind}")))?;
+        let release = id
+            .rsplit('-')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_uppercase();
+        if tag.rsplit('-').next() != Some(release.as_str()) {
+            return Err(fail("marker release does not match id"));
+        }
+        Ok(marker.to_string())
+    }
+}
+
+impl RawSection {
+    fn optional(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn required(&self, key: &str) -> Result<&str, CorridorLoadError> {
+        self.optional(key)
+            .ok_or_else(|| CorridorLoadError::MissingField {
+                section: self.name.clone(),
+                key: key.to_string(),
+            })
+    }
+
+    fn parsed<T>(&self, key: &str, f: impl Fn(&str) -> Option<T>) -> Result<T, CorridorLoadError> {
+        let value = self.required(key)?;
+        f(value).ok_or_else(|| CorridorLoadError::InvalidValue {
+            section: self.name.clone(),
+            key: key.to_string(),
+            value: value.to_string(),
+        })
+    }
+
+    fn flag(&self, key: &str) -> Result<bool, CorridorLoadError> {
+        self.parsed(key, |v| v.parse().ok())
+    }
+
+    /// Guardrail limits must be finite and non-negative.
+    fn limit(&self, key: &str) -> Result<f64, CorridorLoadError> {
+        self.parsed(key, |v| {
+            v.parse::<f64>().ok().filter(|x| x.is_finite() && *x >= 0.0)
+        })
+    }
+
+    fn optional_limit(&self, key: &str) -> Result<Option<f64>, CorridorLoadError> {
+        self.optional(key).map(|_| self.limit(key)).transpose()
+    }
+}
+
+fn unquote(value: &str) -> String {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRIC: &str = include_str!("../../qpudatashards/corridors/tribal.gric-epa-2024.aln");
+    const PHX: &str = include_str!("../../qpudatashards/corridors/city.phx-smartinfra-2024.aln");
+    const CO: &str = include_str!("../../qpudatashards/juris/state.co-neuraldata-2024.aln");
+
+    #[test]
+    fn parses_gric_corridor() {
+        let p = CorridorProfile::parse(GRIC).unwrap();
+        assert_eq!(p.id.tier, CorridorTier::Tribal);
+        assert_eq!(p.id.code, "tribal.gric-epa-2024");
+        assert_eq!(p.id.version, "2024.1");
+        assert_eq!(p.strength, CorridorStrength::HardVeto);
+        assert!(p.fpic.required);
+        assert_eq!(p.fpic.community_veto_scope.len(), 3);
+        assert!(!p.fpic.initial_state().fpic_granted);
+        assert!(p.neurorights.forbids_inner_for_access);
+        assert_eq!(p.eco_guardrails.max_delta_pm25_ug_m3, 0.0);
+        assert_eq!(p.eco_guardrails.max_delta_emissions_co2e, None);
+    }
+
+    #[test]
+    fn parses_phx_corridor_without_fpic() {
+        let p = CorridorProfile::parse(PHX).unwrap();
+        assert_eq!(p.strength, CorridorStrength::AdvisoryOnly);
+        assert!(p.fpic.community_veto_scope.is_empty());
+        assert!(p.fpic.initial_state().fpic_granted);
+        assert_eq!(p.eco_guardrails.max_delta_water_use_m3, 1e5);
+    }
+
+    #[test]
+    fn parses_jurisdiction_profile() {
+        let j = JurisdictionProfile::parse(CO).unwrap();
+        assert_eq!(j.id, "state.co-neuraldata-2024");
+        assert_eq!(j.constraints.get("neural_data_as_sensitive"), Some(&true));
+        assert_eq!(
+            j.applies_to_planes,
+            vec!["bci.hci.eeg", "neuromorph.softwareonly"]
+        );
+    }
+
+    #[test]
+    fn rejects_tampered_proofhex() {
+        let tampered = GRIC.replace("0xCORRIDOR-GRIC-EPA-2024", "0xCORRIDOR-GRIC-EPA-2023");
+        assert!(matches!(
+            CorridorProfile::parse(&tampered),
+            Err(CorridorLoadError::ProofHex { .. })
+        ));
+        let wrong_kind = GRIC.replace("0xCORRIDOR-", "0xJURIS-");
+        assert!(CorridorProfile::parse(&wrong_kind).is_err());
+    }
+
+    #[test]
+    fn rejects_content_after_seal() {
+        let unsealed = format!("{GRIC}\neco_guardrails_extra\n  max_delta_pm25_ug_m3 9.0\n");
+        assert!(matches!(
+            CorridorProfile::parse(&unsealed),
+            Err(CorridorLoadError::Syntax { .. })
+        ));
+    }
+
+    #[test]
+    fn registry_loads_qpudatashards() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../qpudatashards");
+        let registry = CorridorRegistry::load_dir(root).unwrap();
+        let gric = registry.latest("tribal.gric-epa-2024").unwrap();
+        assert!(registry.get(&gric.id).is_some());
+        assert!(registry.jurisdiction("state.co-neuraldata-2024").is_some());
+        assert_eq!(registry.len(), 3);
+    }
+}
@@ -1,14 +1,165 @@
+/** Largest page the server returns. */
+const MAX_PAGE_SIZE = 1000;
+
 export interface NodeView {
   node_id: string;
   contaminant: string;
   k_n: number;
-  ecoimpact_band: number;
+  ecoimpact_score: number;
+  /**
+   * The band fields are `null` for unregulated contaminants and for
+   * contaminants without a configured reference.
+   */
+  k_n_norm: number | null;
+  ecoimpact_band: number | null;
+  ecoimpact_band_label: string | null;
+  binding_authority: string | null;
+  unregulated: boolean;
+}
+
+export interface Page<T> {
+  total: number;
+  offset: number;
+  limit: number;
+  items: T[];
+}
+
+export interface HistoryPoint extends NodeView {
+  generated_at: string;
+}
+
+export interface CorridorView {
+  corridor: {
+    id: { tier: string; code: string; version: string };
+    description: string;
+    strength: string;
+  };
+  nodes: NodeView[];
+  ecoimpact_score: number | null;
+}
+
+export interface Health {
+  status: "ok" | "stale" | "empty";
+  shards: number;
+  latest_shard: string | null;
+  age_seconds: number | null;
+}
+
+export interface NodeQuery {
+  contaminant?: string;
+  /** Band label or numeric value. */
+  band?: string | number;
+  offset?: number;
+  limit?: number;
 }
 
-export async function fetchNodes(baseUrl: string): Promise<NodeView[]> {
-  const resp = await fetch(`${baseUrl}/nodes`);
+export interface HistoryQuery extends NodeQuery {
+  /** RFC 3339, inclusive. */
+  from?: string;
+  /** RFC 3339, exclusive. */
+  to?: string;
+}
+
+async function getJson<T>(url: string, what: string): Promise<T> {
+  const resp = await fetch(url);
   if (!resp.ok) {
-    throw new Error(`failed to fetch nodes: ${resp.status}`);
+    throw new Error(`failed to fetch ${what}: ${resp.status}`);
   }
   return await resp.json();
 }
+
+function queryString(query: object): string {
+  const params = new URLSearchParams();
+  for (const [key, value] of Object.entries(query)) {
+    if (value !== undefined) {
+      params.set(key, String(value));
+    }
+  }
+  const qs = params.toString();
+  return qs ? `?${qs}` : "";
+}
+
+export async function fetchNodes(
+  baseUrl: string,
+  query: NodeQuery = {}
+): Promise<Page<NodeView>> {
+  return getJson(`${baseUrl}/nodes${queryString(query)}`, "nodes");
+}
+
+/** Every node matching `query`, fetched page by page. */
+export async function fetchAllNodes(
+  baseUrl: string,
+  query: Omit<NodeQuery, "offset" | "limit"> = {}
+): Promise<NodeView[]> {
+  const nodes: NodeView[] = [];
+  for (;;) {
+    const page = await fetchNodes(baseUrl, {
+      ...query,
+      offset: nodes.length,
+      limit: MAX_PAGE_SIZE,
+    });
+    nodes.push(...page.items);
+    if (page.items.length === 0 || nodes.length >= page.total) {
+      return nodes;
+    }
+  }
+}
+
+export async function fetchNodeHistory(
+  baseUrl: string,
+  nodeId: string,
+  query: HistoryQuery = {}
+): Promise<Page<HistoryPoint>> {
+  const url = `${baseUrl}/nodes/${encodeURIComponent(nodeId)}/history${queryString(query)}`;
+  return getJson(url, `history for ${nodeId}`);
+}
+
+export async function fetchCorridors(baseUrl: string): Promise<CorridorView[]> {
+  return getJson(`${baseUrl}/corridors`, "corridors");
+}
+
+/** `/healthz` answers 503 when stale or empty, but still reports why. */
+export async function fetchHealth(baseUrl: string): Promise<Health> {
+  const resp = await fetch(`${baseUrl}/healthz`);
+  return await resp.json();
+}
+
+export interface NodeDelta {
+  node_id: string;
+  contaminant: string;
+  k_n: number;
+  previous_k_n: number | null;
+  delta_k_n: number | null;
+  ecoimpact_score: number;
+  band: string | null;
+}
+
+export interface BandAlert {
+  node_id: string;
+  contaminant: string;
+  from: string;
+  to: string;
+  rising: boolean;
+}
+
+export interface ShardEvent {
+  generated_at: string;
+  changes: NodeDelta[];
+  removed: [string, string][];
+  alerts: BandAlert[];
+}
+
+/**
+ * Subscribe to `/events`. `onLagged` fires when the server dropped events for
+ * this subscriber; refetch `/nodes` then. Returns a function that unsubscribes.
+ */
+export function subscribeShards(
+  baseUrl: string,
+  onShard: (event: ShardEvent) => void,
+  onLagged: () => void = () => {}
+): () => void {
+  const source = new EventSource(`${baseUrl}/events`);
+  source.addEventListener("shard", (e) => onShard(JSON.parse((e as MessageEvent).data)));
+  source.addEventListener("lagged", () => onLagged());
+  return () => source.close();
+}
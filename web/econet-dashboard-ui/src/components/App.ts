@@ -1,14 +1,24 @@
-import { fetchNodes } from "../api";
+import { fetchAllNodes, subscribeShards } from "../api";
+import type { NodeView, ShardEvent } from "../api";
 import { NodeCard } from "./NodeCard";
 
-export async function App(baseUrl: string): Promise<void> {
+function nodeKey(nodeId: string, contaminant: string): string {
+  return `${nodeId}\u0000${contaminant}`;
+}
+
+/**
+ * Render every node, then keep the cards current from `/events`. Returns a
+ * function that unsubscribes.
+ */
+export async function App(baseUrl: string): Promise<() => void> {
   const root = document.getElementById("app");
   if (!root) {
-    return;
+    return () => {};
   }
-  try {
-    const nodes = await fetchNodes(baseUrl);
-    const cards = nodes.map(NodeCard).join("");
+  const nodes = new Map<string, NodeView>();
+
+  const render = () => {
+    const cards = [...nodes.values()].map(NodeCard).join("");
     root.innerHTML = `
       <main>
         <h1>Phoenix EcoNet Corridor</h1>
@@ -17,7 +27,46 @@ export async function App(baseUrl: string): Promise<void> {
         </section>
       </main>
     `;
-  } catch (err) {
+  };
+  const fail = (err: unknown) => {
     root.innerHTML = `<p>Failed to load nodes: ${(err as Error).message}</p>`;
+  };
+  const reload = async () => {
+    const fresh = await fetchAllNodes(baseUrl);
+    nodes.clear();
+    for (const node of fresh) {
+      nodes.set(nodeKey(node.node_id, node.contaminant), node);
+    }
+    render();
+  };
+
+  // Events carry K_n and the score but not every `NodeView` field, so new
+  // nodes and band changes are refetched. `k_n_norm` is refreshed only then.
+  const apply = (event: ShardEvent) => {
+    const known = event.changes.every((c) => nodes.has(nodeKey(c.node_id, c.contaminant)));
+    if (!known || event.alerts.length > 0) {
+      reload().catch(fail);
+      return;
+    }
+    for (const [nodeId, contaminant] of event.removed) {
+      nodes.delete(nodeKey(nodeId, contaminant));
+    }
+    for (const change of event.changes) {
+      const node = nodes.get(nodeKey(change.node_id, change.contaminant));
+      if (node) {
+        node.k_n = change.k_n;
+        node.ecoimpact_score = change.ecoimpact_score;
+      }
+    }
+    render();
+  };
+
+  try {
+    await reload();
+  } catch (err) {
+    fail(err);
   }
+  return subscribeShards(baseUrl, apply, () => {
+    reload().catch(fail);
+  });
 }
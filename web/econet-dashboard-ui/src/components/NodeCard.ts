@@ -2,11 +2,7 @@ import type { NodeView } from "../api";
 
 export function NodeCard(node: NodeView): string {
   const bandLabel =
-    node.ecoimpact_band < 0.3
-      ? "low"
-      : node.ecoimpact_band < 0.7
-      ? "solid"
-      : "high";
+    node.ecoimpact_band_label ?? (node.unregulated ? "unregulated" : "unbanded");
 
   return `
     <div class="node-card">